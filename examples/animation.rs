@@ -0,0 +1,41 @@
+use std::time::{Duration, Instant};
+
+use openrgb2::{Color, FrameScheduler, OpenRgbClient, OpenRgbResult};
+
+#[tokio::main]
+async fn main() -> OpenRgbResult<()> {
+    // connect to local server
+    let client = OpenRgbClient::connect().await?;
+    let group = client.get_all_controllers().await?;
+    group.set_controllable_mode().await?;
+
+    let start = Instant::now();
+    let mut scheduler = FrameScheduler::new(60)?;
+    // move a single red LED along every controller for 10 seconds
+    scheduler
+        .run_for(&group, Duration::from_secs(10), |group, t| {
+            let step = (t - start).as_millis() as usize / 50;
+            let mut cmd = group.cmd();
+            for c in group {
+                let mut colors = vec![Color::default(); c.num_leds()];
+                if let Some(led) = colors.get_mut(step % c.num_leds().max(1)) {
+                    *led = Color::new(255, 0, 0);
+                }
+                cmd.set_controller_leds(c, colors)
+                    .expect("colors fit the controller");
+            }
+            cmd
+        })
+        .await?;
+
+    let stats = scheduler.stats();
+    println!(
+        "{} frames at {:.1} FPS ({} late, {} dropped), median write latency: {:?}",
+        stats.frames(),
+        stats.achieved_fps(),
+        stats.late_frames(),
+        stats.dropped_frames(),
+        stats.median_latency(),
+    );
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use std::time::{Duration, Instant};
+
+use openrgb2::{
+    Color, FrameScheduler, OpenRgbClient, OpenRgbResult,
+    effects::{Comet, Effect, Fire, RainbowWave},
+};
+
+#[tokio::main]
+async fn main() -> OpenRgbResult<()> {
+    // connect to local server
+    let client = OpenRgbClient::connect().await?;
+    let group = client.get_all_controllers().await?;
+    group.set_controllable_mode().await?;
+
+    let effects: [Box<dyn Effect>; 3] = [
+        Box::new(RainbowWave::default()),
+        Box::new(Comet::new(Color::new(0, 128, 255))),
+        Box::new(Fire::default()),
+    ];
+
+    let mut scheduler = FrameScheduler::new(60)?;
+    for effect in effects {
+        let start = Instant::now();
+        // effects render to a `CommandGroup`, which the scheduler executes every frame
+        scheduler
+            .run_for(&group, Duration::from_secs(5), |group, t| {
+                effect.render_group(group, t - start)
+            })
+            .await?;
+    }
+    Ok(())
+}
@@ -17,10 +17,10 @@ async fn main() -> OpenRgbResult<()> {
     let segment = zone.get_segment(0)?;
     println!("Segment: {}", segment.name());
 
-    // this will set the rest of the zone LEDs to black
+    // the rest of the zone keeps the colors last written to it
     segment.set_all_leds(Color::new(0, 255, 0)).await?;
 
-    // instead we can do:
+    // or set the whole zone in a single command:
     let mut cmd = segment.cmd();
     // set "background" by specifying colors for the zone
     cmd.set_zone_leds(zone.id(), vec![Color::new(255, 0, 0); zone.num_leds()])?;
@@ -0,0 +1,199 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+};
+
+use crate::Color;
+
+/// Brightness factor that can be shared between a client and its controllers.
+#[derive(Debug, Clone)]
+pub(crate) struct Brightness(Arc<AtomicU32>);
+
+impl Default for Brightness {
+    fn default() -> Self {
+        Self(Arc::new(AtomicU32::new(1.0_f32.to_bits())))
+    }
+}
+
+impl Brightness {
+    pub(crate) fn get(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    /// Sets the brightness, clamped to `0.0..=1.0`.
+    pub(crate) fn set(&self, brightness: f32) {
+        let brightness = if brightness.is_nan() {
+            1.0
+        } else {
+            brightness.clamp(0.0, 1.0)
+        };
+        self.0.store(brightness.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Scales a single channel by `factor`, rounding down so the result never exceeds the exact value.
+fn scale_channel(value: u8, factor: f32) -> u8 {
+    (f32::from(value) * factor) as u8
+}
+
+/// Scales `colors` by `factor`, which should be in `0.0..=1.0`.
+pub(crate) fn scale_colors(colors: &mut [Color], factor: f32) {
+    if factor >= 1.0 {
+        return;
+    }
+    for color in colors {
+        *color = Color::new(
+            scale_channel(color.r, factor),
+            scale_channel(color.g, factor),
+            scale_channel(color.b, factor),
+        );
+    }
+}
+
+/// Limits the estimated current drawn by a controller.
+///
+/// The current of a single LED is estimated as `ma_per_channel` per fully lit channel,
+/// so full white draws `3 * ma_per_channel`.
+/// Frames that would draw more than `budget_ma` are scaled down uniformly to fit the budget.
+///
+/// A [`crate::Controller`] evaluates the limit against its whole frame:
+/// the colors it last sent to the other LEDs are taken into account,
+/// and only the LEDs being written are scaled down, see [`Self::apply_in_frame()`].
+/// Writing all LEDs at once (e.g. through [`crate::Command`]) spreads the reduction evenly.
+///
+/// # Example
+///
+/// ```
+/// # use openrgb2::{Color, PowerLimit};
+/// // 60 WS2812 LEDs on a 2A header
+/// let limit = PowerLimit::new(PowerLimit::WS2812_MA_PER_CHANNEL, 2000.0);
+/// let mut colors = vec![Color::new(255, 255, 255); 60];
+/// assert_eq!(limit.estimate_ma(&colors), 3600.0);
+/// limit.apply(&mut colors);
+/// assert!(limit.estimate_ma(&colors) <= 2000.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerLimit {
+    /// Current drawn by a single fully lit channel, in milliampere.
+    pub ma_per_channel: f32,
+
+    /// Maximum current all LEDs of a controller may draw together, in milliampere.
+    pub budget_ma: f32,
+}
+
+impl PowerLimit {
+    /// Typical current of a single WS2812 (ARGB) channel at full brightness.
+    pub const WS2812_MA_PER_CHANNEL: f32 = 20.0;
+
+    /// Creates a new power limit.
+    pub fn new(ma_per_channel: f32, budget_ma: f32) -> Self {
+        Self {
+            ma_per_channel,
+            budget_ma,
+        }
+    }
+
+    /// Returns the estimated current drawn by `colors`, in milliampere.
+    pub fn estimate_ma(&self, colors: &[Color]) -> f32 {
+        let total = colors
+            .iter()
+            .map(|c| u32::from(c.r) + u32::from(c.g) + u32::from(c.b))
+            .sum::<u32>();
+        total as f32 / 255.0 * self.ma_per_channel
+    }
+
+    /// Returns the factor `colors` have to be scaled by to stay within the budget.
+    ///
+    /// This is 1.0 if `colors` are already within the budget.
+    pub fn scale_factor(&self, colors: &[Color]) -> f32 {
+        let estimate = self.estimate_ma(colors);
+        match estimate > self.budget_ma {
+            true => (self.budget_ma / estimate).max(0.0),
+            false => 1.0,
+        }
+    }
+
+    /// Scales `colors` down to stay within the budget and returns the factor that was applied.
+    pub fn apply(&self, colors: &mut [Color]) -> f32 {
+        let factor = self.scale_factor(colors);
+        scale_colors(colors, factor);
+        factor
+    }
+
+    /// Returns the factor `colors` have to be scaled by, so that together with `others`,
+    /// the colors of the LEDs that are not written, they stay within the budget.
+    ///
+    /// This is 0.0 if `others` alone already exceed the budget.
+    pub fn scale_factor_in_frame(&self, others: &[Color], colors: &[Color]) -> f32 {
+        let written = self.estimate_ma(colors);
+        let remaining = self.budget_ma - self.estimate_ma(others);
+        match written > remaining {
+            true if written > 0.0 => (remaining / written).clamp(0.0, 1.0),
+            _ => 1.0,
+        }
+    }
+
+    /// Scales `colors` down so the whole frame, `colors` together with `others`, stays within the budget.
+    ///
+    /// Returns the factor that was applied to `colors`, `others` are not changed.
+    pub fn apply_in_frame(&self, others: &[Color], colors: &mut [Color]) -> f32 {
+        let factor = self.scale_factor_in_frame(others, colors);
+        scale_colors(colors, factor);
+        factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_brightness() {
+        let brightness = Brightness::default();
+        let shared = brightness.clone();
+        assert!((brightness.get() - 1.0).abs() < f32::EPSILON);
+        shared.set(0.25);
+        assert!((brightness.get() - 0.25).abs() < f32::EPSILON);
+        shared.set(3.0);
+        assert!((brightness.get() - 1.0).abs() < f32::EPSILON);
+        shared.set(-1.0);
+        assert!(brightness.get().abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_power_limit() {
+        let limit = PowerLimit::new(20.0, 120.0);
+        let mut colors = vec![Color::new(255, 0, 0); 3];
+        assert!((limit.estimate_ma(&colors) - 60.0).abs() < 1e-3);
+        assert!((limit.apply(&mut colors) - 1.0).abs() < f32::EPSILON);
+        assert_eq!(colors, vec![Color::new(255, 0, 0); 3]);
+
+        let mut colors = vec![Color::new(255, 255, 255); 4];
+        assert!((limit.estimate_ma(&colors) - 240.0).abs() < 1e-3);
+        assert!((limit.apply(&mut colors) - 0.5).abs() < f32::EPSILON);
+        assert_eq!(colors, vec![Color::new(127, 127, 127); 4]);
+        assert!(limit.estimate_ma(&colors) <= 120.0);
+    }
+
+    #[test]
+    fn test_power_limit_in_frame() {
+        // two zones of 2 LEDs, written one after the other
+        let limit = PowerLimit::new(20.0, 120.0);
+        let mut first = vec![Color::new(255, 0, 0); 2];
+        let unlit = vec![Color::new(0, 0, 0); 2];
+        assert!((limit.apply_in_frame(&unlit, &mut first) - 1.0).abs() < f32::EPSILON);
+
+        // 40 mA are used by the first zone, 80 mA remain for 120 mA of white
+        let mut second = vec![Color::new(255, 255, 255); 2];
+        assert!((limit.apply_in_frame(&first, &mut second) - 2.0 / 3.0).abs() < 1e-6);
+        assert_eq!(second, vec![Color::new(170, 170, 170); 2]);
+        let frame = [first.as_slice(), second.as_slice()].concat();
+        assert!(limit.estimate_ma(&frame) <= 120.0);
+
+        // the rest of the frame already uses the whole budget
+        let full = vec![Color::new(255, 255, 255); 2];
+        let mut colors = vec![Color::new(255, 0, 0); 2];
+        assert!(limit.apply_in_frame(&full, &mut colors).abs() < f32::EPSILON);
+        assert_eq!(colors, unlit);
+    }
+}
@@ -0,0 +1,482 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Color, Controller};
+
+/// Order in which a device interprets the color channels it receives.
+///
+/// Colors are reordered right before they are sent,
+/// so a [`Color`] displays correctly on LEDs that are wired in a different order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ChannelOrder {
+    /// Red, green, blue (no reordering).
+    #[default]
+    Rgb,
+    /// Red, blue, green.
+    Rbg,
+    /// Green, red, blue.
+    Grb,
+    /// Green, blue, red.
+    Gbr,
+    /// Blue, red, green.
+    Brg,
+    /// Blue, green, red.
+    Bgr,
+}
+
+impl ChannelOrder {
+    /// Reorders the channels of `color` to this order.
+    pub fn apply(self, color: Color) -> Color {
+        let Color { r, g, b } = color;
+        match self {
+            Self::Rgb => Color::new(r, g, b),
+            Self::Rbg => Color::new(r, b, g),
+            Self::Grb => Color::new(g, r, b),
+            Self::Gbr => Color::new(g, b, r),
+            Self::Brg => Color::new(b, r, g),
+            Self::Bgr => Color::new(b, g, r),
+        }
+    }
+}
+
+/// Color transform that corrects for the way a device renders colors.
+///
+/// The transform is applied in the following order:
+/// 1. Each channel is multiplied by its gain (white balance).
+/// 2. The gamma curve `out = in ^ gamma` is applied.
+/// 3. The result is scaled down to the maximum brightness.
+/// 4. The channels are reordered.
+///
+/// # Example
+///
+/// ```
+/// # use openrgb2::{Calibration, ChannelOrder, Color};
+/// // green tinted RAM that is wired as GRB
+/// let calibration = Calibration::new()
+///     .with_gains(1.0, 0.8, 1.0)
+///     .with_channel_order(ChannelOrder::Grb);
+/// assert_eq!(calibration.apply(Color::new(255, 255, 0)), Color::new(204, 255, 0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(from = "CalibrationSettings", into = "CalibrationSettings")
+)]
+pub struct Calibration {
+    settings: CalibrationSettings,
+    /// Per channel lookup table combining gain, gamma and brightness.
+    tables: Box<[[u8; 256]; 3]>,
+}
+
+/// Parameters of a [`Calibration`], which is also its serialized form.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+struct CalibrationSettings {
+    gains: [f32; 3],
+    gamma: f32,
+    max_brightness: f32,
+    channel_order: ChannelOrder,
+}
+
+impl Default for CalibrationSettings {
+    fn default() -> Self {
+        Self {
+            gains: [1.0; 3],
+            gamma: 1.0,
+            max_brightness: 1.0,
+            channel_order: ChannelOrder::Rgb,
+        }
+    }
+}
+
+impl CalibrationSettings {
+    /// Replaces values that would produce a broken lookup table, see the setters of [`Calibration`].
+    ///
+    /// Settings loaded with serde skip those setters, so this runs for every [`Calibration`].
+    fn sanitized(self) -> Self {
+        Self {
+            gains: self
+                .gains
+                .map(|gain| if gain.is_finite() { gain.max(0.0) } else { 1.0 }),
+            gamma: if self.gamma > 0.0 && self.gamma.is_finite() {
+                self.gamma
+            } else {
+                1.0
+            },
+            max_brightness: if self.max_brightness.is_nan() {
+                1.0
+            } else {
+                self.max_brightness.clamp(0.0, 1.0)
+            },
+            channel_order: self.channel_order,
+        }
+    }
+}
+
+impl From<CalibrationSettings> for Calibration {
+    fn from(settings: CalibrationSettings) -> Self {
+        let settings = settings.sanitized();
+        let mut tables = Box::new([[0; 256]; 3]);
+        for (table, gain) in tables.iter_mut().zip(settings.gains) {
+            for (i, v) in table.iter_mut().enumerate() {
+                let value = (i as f32 / 255.0 * gain).clamp(0.0, 1.0);
+                *v = (value.powf(settings.gamma) * settings.max_brightness * 255.0).round() as u8;
+            }
+        }
+        Self { settings, tables }
+    }
+}
+
+impl From<Calibration> for CalibrationSettings {
+    fn from(calibration: Calibration) -> Self {
+        calibration.settings
+    }
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        CalibrationSettings::default().into()
+    }
+}
+
+impl Calibration {
+    /// Creates a calibration that leaves colors unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the gain of the red, green and blue channel.
+    ///
+    /// Negative gains are treated as 0, NaN or infinite gains as 1.0.
+    #[must_use]
+    pub fn with_gains(self, r: f32, g: f32, b: f32) -> Self {
+        CalibrationSettings {
+            gains: [r, g, b],
+            ..self.settings
+        }
+        .into()
+    }
+
+    /// Sets the gamma curve, 1.0 means no correction.
+    ///
+    /// Non-positive, NaN or infinite values are treated as 1.0.
+    #[must_use]
+    pub fn with_gamma(self, gamma: f32) -> Self {
+        CalibrationSettings {
+            gamma,
+            ..self.settings
+        }
+        .into()
+    }
+
+    /// Sets the maximum brightness, clamped to `0.0..=1.0`. NaN is treated as 1.0.
+    #[must_use]
+    pub fn with_max_brightness(self, max_brightness: f32) -> Self {
+        CalibrationSettings {
+            max_brightness,
+            ..self.settings
+        }
+        .into()
+    }
+
+    /// Sets the channel order of the device.
+    #[must_use]
+    pub fn with_channel_order(self, channel_order: ChannelOrder) -> Self {
+        CalibrationSettings {
+            channel_order,
+            ..self.settings
+        }
+        .into()
+    }
+
+    /// Returns the gains of the red, green and blue channel.
+    pub fn gains(&self) -> [f32; 3] {
+        self.settings.gains
+    }
+
+    /// Returns the gamma of this calibration.
+    pub fn gamma(&self) -> f32 {
+        self.settings.gamma
+    }
+
+    /// Returns the maximum brightness of this calibration.
+    pub fn max_brightness(&self) -> f32 {
+        self.settings.max_brightness
+    }
+
+    /// Returns the channel order of this calibration.
+    pub fn channel_order(&self) -> ChannelOrder {
+        self.settings.channel_order
+    }
+
+    /// Returns true if this calibration leaves colors unchanged.
+    pub fn is_identity(&self) -> bool {
+        self.settings == CalibrationSettings::default()
+    }
+
+    /// Applies this calibration to `color`.
+    pub fn apply(&self, color: Color) -> Color {
+        let [r, g, b] = &*self.tables;
+        let color = Color::new(
+            r[color.r as usize],
+            g[color.g as usize],
+            b[color.b as usize],
+        );
+        self.settings.channel_order.apply(color)
+    }
+
+    /// Applies this calibration to all `colors` in place.
+    pub fn apply_all(&self, colors: &mut [Color]) {
+        if self.is_identity() {
+            return;
+        }
+        for color in colors {
+            *color = self.apply(*color);
+        }
+    }
+}
+
+/// Calibration of a single controller, with optional overrides per zone.
+///
+/// Set it using [`Controller::set_calibration()`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ControllerCalibration {
+    /// Calibration for zones that have no override.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub default: Calibration,
+
+    /// Calibration overrides keyed by zone name.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "HashMap::is_empty")
+    )]
+    pub zones: HashMap<String, Calibration>,
+}
+
+impl ControllerCalibration {
+    /// Creates a controller calibration that uses `default` for every zone.
+    pub fn new(default: Calibration) -> Self {
+        Self {
+            default,
+            zones: HashMap::new(),
+        }
+    }
+
+    /// Overrides the calibration of the zone named `zone_name`.
+    #[must_use]
+    pub fn with_zone(mut self, zone_name: impl Into<String>, calibration: Calibration) -> Self {
+        self.zones.insert(zone_name.into(), calibration);
+        self
+    }
+
+    /// Returns the calibration used for the zone named `zone_name`.
+    pub fn for_zone(&self, zone_name: &str) -> &Calibration {
+        self.zones.get(zone_name).unwrap_or(&self.default)
+    }
+}
+
+impl From<Calibration> for ControllerCalibration {
+    fn from(default: Calibration) -> Self {
+        Self::new(default)
+    }
+}
+
+/// Calibrations for multiple controllers, keyed by controller serial or name.
+///
+/// A controller is matched by its serial first, so identical devices can be told apart.
+/// When no entry exists for the serial, the controller name is used instead.
+///
+/// With the `toml` feature enabled this can be read from a file:
+///
+/// ```toml
+/// ["Corsair Vengeance Pro RGB"]
+/// gains = [1.0, 0.8, 1.0]
+/// gamma = 2.2
+///
+/// ["ABC123"]
+/// max_brightness = 0.5
+/// channel_order = "grb"
+///
+/// ["ABC123".zones."Fan 1"]
+/// gains = [1.0, 1.0, 0.7]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct CalibrationConfig {
+    controllers: HashMap<String, ControllerCalibration>,
+}
+
+impl CalibrationConfig {
+    /// Creates an empty calibration config.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a calibration for the controller with the given serial or name.
+    pub fn insert(
+        &mut self,
+        serial_or_name: impl Into<String>,
+        calibration: impl Into<ControllerCalibration>,
+    ) {
+        self.controllers
+            .insert(serial_or_name.into(), calibration.into());
+    }
+
+    /// Returns the calibration for `controller`, if there is one.
+    pub fn get(&self, controller: &Controller) -> Option<&ControllerCalibration> {
+        self.lookup(controller.serial(), controller.name())
+    }
+
+    fn lookup(&self, serial: &str, name: &str) -> Option<&ControllerCalibration> {
+        let by_serial = match serial.is_empty() {
+            true => None,
+            false => self.controllers.get(serial),
+        };
+        by_serial.or_else(|| self.controllers.get(name))
+    }
+
+    /// Parses a calibration config from a TOML string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a valid calibration config.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> crate::OpenRgbResult<Self> {
+        super::toml_file::from_str(s)
+    }
+
+    /// Reads a calibration config from a TOML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid calibration config.
+    #[cfg(feature = "toml")]
+    pub fn load(path: impl AsRef<std::path::Path>) -> crate::OpenRgbResult<Self> {
+        Self::from_toml(&super::toml_file::read(path.as_ref())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity() {
+        let calibration = Calibration::new();
+        assert!(calibration.is_identity());
+        for v in [0, 1, 127, 128, 254, 255] {
+            let color = Color::new(v, 255 - v, v / 2);
+            assert_eq!(calibration.apply(color), color);
+        }
+    }
+
+    #[test]
+    fn test_transform() {
+        let calibration = Calibration::new()
+            .with_gains(1.0, 0.5, 2.0)
+            .with_max_brightness(0.5);
+        assert!(!calibration.is_identity());
+        assert_eq!(
+            calibration.apply(Color::new(255, 255, 100)),
+            Color::new(128, 64, 100)
+        );
+
+        let calibration = Calibration::new().with_gamma(2.0);
+        assert_eq!(
+            calibration.apply(Color::new(255, 128, 0)),
+            Color::new(255, 64, 0)
+        );
+    }
+
+    #[test]
+    fn test_channel_order() {
+        let color = Color::new(1, 2, 3);
+        assert_eq!(ChannelOrder::Rgb.apply(color), Color::new(1, 2, 3));
+        assert_eq!(ChannelOrder::Grb.apply(color), Color::new(2, 1, 3));
+        assert_eq!(ChannelOrder::Bgr.apply(color), Color::new(3, 2, 1));
+        assert_eq!(ChannelOrder::Gbr.apply(color), Color::new(2, 3, 1));
+    }
+
+    #[test]
+    fn test_lookup() {
+        let mut config = CalibrationConfig::new();
+        config.insert("RAM", Calibration::new().with_gains(1.0, 0.8, 1.0));
+        config.insert("ABC123", Calibration::new().with_gamma(2.2));
+
+        let by_serial = config.lookup("ABC123", "RAM").unwrap();
+        assert!((by_serial.default.gamma() - 2.2).abs() < f32::EPSILON);
+        let by_name = config.lookup("XYZ", "RAM").unwrap();
+        assert_eq!(by_name.default.gains(), [1.0, 0.8, 1.0]);
+        assert!(config.lookup("", "Fan").is_none());
+    }
+
+    #[test]
+    fn test_zone_override() {
+        let fan = Calibration::new().with_gains(1.0, 1.0, 0.5);
+        let calibration = ControllerCalibration::default().with_zone("Fan 1", fan.clone());
+        assert_eq!(calibration.for_zone("Fan 1"), &fan);
+        assert!(calibration.for_zone("Fan 2").is_identity());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_from_toml() -> crate::OpenRgbResult<()> {
+        let config = CalibrationConfig::from_toml(
+            r#"
+            ["Corsair Vengeance Pro RGB"]
+            gains = [1.0, 0.8, 1.0]
+            gamma = 2.2
+
+            ["ABC123"]
+            max_brightness = 0.5
+            channel_order = "grb"
+
+            ["ABC123".zones."Fan 1"]
+            gains = [1.0, 1.0, 0.7]
+            "#,
+        )?;
+        let ram = config.lookup("", "Corsair Vengeance Pro RGB").unwrap();
+        assert_eq!(ram.default.gains(), [1.0, 0.8, 1.0]);
+        assert!((ram.default.gamma() - 2.2).abs() < f32::EPSILON);
+
+        let fans = config.lookup("ABC123", "Fans").unwrap();
+        assert_eq!(fans.default.channel_order(), ChannelOrder::Grb);
+        assert_eq!(fans.for_zone("Fan 1").gains(), [1.0, 1.0, 0.7]);
+        assert_eq!(fans.for_zone("Fan 2").gains(), [1.0; 3]);
+
+        assert!(CalibrationConfig::from_toml("[\"RAM\"]\ngamma = \"high\"").is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_from_toml_invalid_values() -> crate::OpenRgbResult<()> {
+        let config = CalibrationConfig::from_toml(
+            r#"
+            ["RAM"]
+            gains = [nan, -1.0, inf]
+            gamma = -1.0
+            max_brightness = 2.0
+            "#,
+        )?;
+        let ram = &config.lookup("", "RAM").unwrap().default;
+        assert_eq!(ram.gains(), [1.0, 0.0, 1.0]);
+        assert!((ram.gamma() - 1.0).abs() < f32::EPSILON);
+        assert!((ram.max_brightness() - 1.0).abs() < f32::EPSILON);
+        // black stays off
+        assert_eq!(ram.apply(Color::new(0, 0, 0)), Color::new(0, 0, 0));
+        assert_eq!(
+            ram.apply(Color::new(255, 255, 255)),
+            Color::new(255, 0, 255)
+        );
+        Ok(())
+    }
+}
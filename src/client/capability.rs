@@ -0,0 +1,322 @@
+use std::fmt::Write;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ColorMode, Controller, ControllerMode, DeviceType, Direction, ModeFlag, client::mode::in_range,
+};
+
+/// Inclusive range of values reported by a device.
+///
+/// Some devices report a `min` that is larger than `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ValueRange {
+    /// Minimum value.
+    pub min: u32,
+    /// Maximum value.
+    pub max: u32,
+}
+
+impl ValueRange {
+    /// Returns true if `value` is in this range, also when `min > max`.
+    pub fn contains(&self, value: u32) -> bool {
+        in_range(self.min, self.max, value)
+    }
+}
+
+/// How a mode is persisted to the device, based on [`ModeFlag::ManualSave`] and [`ModeFlag::AutomaticSave`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum SaveBehaviour {
+    /// The mode can not be saved.
+    #[default]
+    None,
+    /// The mode can be saved with [`Controller::save_mode()`].
+    Manual,
+    /// The device saves the mode when it is set.
+    Automatic,
+}
+
+/// Summary of what a single mode of a controller supports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ModeCapabilities {
+    /// Index of the mode in the controller.
+    pub id: usize,
+    /// Name of the mode.
+    pub name: String,
+    /// Speed range, if the mode has a speed.
+    pub speed: Option<ValueRange>,
+    /// Brightness range, if the mode has a brightness.
+    pub brightness: Option<ValueRange>,
+    /// Supported directions, empty if the mode has no direction.
+    pub directions: Vec<Direction>,
+    /// Supported color modes, [`ColorMode::None`] is not included.
+    pub color_modes: Vec<ColorMode>,
+    /// Number of mode specific color slots, if the mode has mode specific colors.
+    pub colors: Option<ValueRange>,
+    /// How this mode is saved to the device.
+    pub save: SaveBehaviour,
+}
+
+impl ModeCapabilities {
+    pub(crate) fn new(mode: &ControllerMode<'_>) -> Self {
+        let range = |min: Option<u32>, max: Option<u32>| {
+            Some(ValueRange {
+                min: min?,
+                max: max?,
+            })
+        };
+        let flags = mode.flags();
+        let save = if flags.contains(ModeFlag::AutomaticSave) {
+            SaveBehaviour::Automatic
+        } else if flags.contains(ModeFlag::ManualSave) {
+            SaveBehaviour::Manual
+        } else {
+            SaveBehaviour::None
+        };
+        Self {
+            id: mode.id(),
+            name: mode.name().to_owned(),
+            speed: range(mode.speed_min(), mode.speed_max()),
+            brightness: range(mode.brightness_min(), mode.brightness_max()),
+            directions: mode.supported_directions(),
+            color_modes: [
+                ColorMode::PerLED,
+                ColorMode::ModeSpecific,
+                ColorMode::Random,
+            ]
+            .into_iter()
+            .filter(|c| mode.supports_color_mode(*c))
+            .collect(),
+            colors: flags
+                .contains(ModeFlag::HasModeSpecificColor)
+                .then(|| range(mode.colors_min(), mode.colors_max()))
+                .flatten(),
+            save,
+        }
+    }
+
+    /// Returns true if this mode supports `color_mode`.
+    pub fn supports_color_mode(&self, color_mode: ColorMode) -> bool {
+        color_mode == ColorMode::None || self.color_modes.contains(&color_mode)
+    }
+}
+
+/// Summary of a controller and the modes it supports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ControllerCapabilities {
+    /// Id of the controller.
+    pub id: usize,
+    /// Name of the controller.
+    pub name: String,
+    /// Vendor of the controller.
+    pub vendor: String,
+    /// Type of the controller.
+    pub device_type: DeviceType,
+    /// Serial of the controller.
+    pub serial: String,
+    /// Location of the controller.
+    pub location: String,
+    /// Number of LEDs in the controller.
+    pub num_leds: usize,
+    /// Name of the active mode.
+    pub active_mode: String,
+    /// All modes of the controller.
+    pub modes: Vec<ModeCapabilities>,
+}
+
+impl ControllerCapabilities {
+    pub(crate) fn new(controller: &Controller) -> Self {
+        Self {
+            id: controller.id(),
+            name: controller.name().to_owned(),
+            vendor: controller.vendor().to_owned(),
+            device_type: controller.device_type(),
+            serial: controller.serial().to_owned(),
+            location: controller.location().to_owned(),
+            num_leds: controller.num_leds(),
+            active_mode: controller.active_mode().name().to_owned(),
+            modes: controller
+                .mode_iter()
+                .map(|m| ModeCapabilities::new(&m))
+                .collect(),
+        }
+    }
+
+    /// Returns the mode named `name`, ignoring case.
+    pub fn mode(&self, name: &str) -> Option<&ModeCapabilities> {
+        self.modes
+            .iter()
+            .find(|m| m.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Capabilities of a group of controllers, see [`crate::ControllerGroup::capabilities()`].
+///
+/// Can be exported as Markdown with [`Self::to_markdown()`],
+/// or serialized (e.g. to JSON) with the `serde` feature.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct CapabilityReport {
+    /// Capabilities per controller.
+    pub controllers: Vec<ControllerCapabilities>,
+}
+
+impl CapabilityReport {
+    /// Returns the controllers that have a mode named `name` (ignoring case) for which `f` returns true.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use openrgb2::*;
+    /// # fn example(report: &CapabilityReport) {
+    /// let breathing = report
+    ///     .with_mode("Breathing", |m| m.supports_color_mode(ColorMode::ModeSpecific))
+    ///     .map(|(controller, _)| &controller.name)
+    ///     .collect::<Vec<_>>();
+    /// # }
+    /// ```
+    pub fn with_mode<'a, F>(
+        &'a self,
+        name: &'a str,
+        f: F,
+    ) -> impl Iterator<Item = (&'a ControllerCapabilities, &'a ModeCapabilities)>
+    where
+        F: Fn(&ModeCapabilities) -> bool + 'a,
+    {
+        self.controllers
+            .iter()
+            .filter_map(move |c| c.mode(name).filter(|m| f(m)).map(|m| (c, m)))
+    }
+
+    /// Renders this report as a Markdown document with a table of modes per controller.
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        for c in &self.controllers {
+            let _ = writeln!(md, "## {} ({})\n", escape(&c.name), c.id);
+            let _ = writeln!(md, "- Vendor: {}", escape(&c.vendor));
+            let _ = writeln!(md, "- Type: {:?}", c.device_type);
+            let _ = writeln!(md, "- Serial: {}", escape(&c.serial));
+            let _ = writeln!(md, "- Location: {}", escape(&c.location));
+            let _ = writeln!(md, "- LEDs: {}", c.num_leds);
+            let _ = writeln!(md, "- Active mode: {}\n", escape(&c.active_mode));
+            md.push_str(
+                "| Mode | Speed | Brightness | Directions | Color modes | Colors | Save |\n",
+            );
+            md.push_str("|---|---|---|---|---|---|---|\n");
+            for m in &c.modes {
+                let _ = writeln!(
+                    md,
+                    "| {} | {} | {} | {} | {} | {} | {:?} |",
+                    escape(&m.name),
+                    fmt_range(m.speed),
+                    fmt_range(m.brightness),
+                    fmt_list(&m.directions),
+                    fmt_list(&m.color_modes),
+                    fmt_range(m.colors),
+                    m.save,
+                );
+            }
+            md.push('\n');
+        }
+        md
+    }
+}
+
+fn fmt_range(range: Option<ValueRange>) -> String {
+    range.map_or_else(|| "-".to_owned(), |r| format!("{}..={}", r.min, r.max))
+}
+
+fn fmt_list<T: std::fmt::Debug>(items: &[T]) -> String {
+    if items.is_empty() {
+        return "-".to_owned();
+    }
+    items
+        .iter()
+        .map(|i| format!("{i:?}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Escapes characters that would break a Markdown table.
+fn escape(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> CapabilityReport {
+        let mode = |name: &str, color_modes: Vec<ColorMode>| ModeCapabilities {
+            id: 0,
+            name: name.to_owned(),
+            speed: Some(ValueRange { min: 300, max: 100 }),
+            brightness: None,
+            directions: vec![Direction::Left, Direction::Right],
+            color_modes,
+            colors: Some(ValueRange { min: 1, max: 2 }),
+            save: SaveBehaviour::Manual,
+        };
+        let controller = |id: usize, name: &str, modes| ControllerCapabilities {
+            id,
+            name: name.to_owned(),
+            vendor: "Vendor".to_owned(),
+            device_type: DeviceType::LEDStrip,
+            serial: String::new(),
+            location: "HID: /dev/hidraw0".to_owned(),
+            num_leds: 10,
+            active_mode: "Direct".to_owned(),
+            modes,
+        };
+        CapabilityReport {
+            controllers: vec![
+                controller(
+                    0,
+                    "Strip | A",
+                    vec![mode("Breathing", vec![ColorMode::ModeSpecific])],
+                ),
+                controller(
+                    1,
+                    "Strip B",
+                    vec![mode("breathing", vec![ColorMode::Random])],
+                ),
+                controller(
+                    2,
+                    "Strip C",
+                    vec![mode("Static", vec![ColorMode::ModeSpecific])],
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_with_mode() {
+        let report = report();
+        let ids = report
+            .with_mode("BREATHING", |m| {
+                m.supports_color_mode(ColorMode::ModeSpecific)
+            })
+            .map(|(c, _)| c.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [0]);
+        assert_eq!(report.with_mode("Breathing", |_| true).count(), 2);
+        assert!(ValueRange { min: 300, max: 100 }.contains(200));
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let md = report().to_markdown();
+        assert!(md.contains("## Strip \\| A (0)"));
+        assert!(md.contains(
+            "| Breathing | 300..=100 | - | Left, Right | ModeSpecific | 1..=2 | Manual |"
+        ));
+        assert_eq!(md.matches("| Mode |").count(), 3);
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::{
-    Color, Controller, OpenRgbError, OpenRgbResult,
+    Color, Controller, OpenRgbError, OpenRgbResult, Zone, ZoneMatrix,
     client::group::{ControllerGroup, ControllerIndex},
 };
 
@@ -154,6 +154,42 @@ impl<'a> CommandGroup<'a> {
         let cmd = self.get_cmd_mut(controller_id)?;
         cmd.set_segment_leds(zone_id, segment_id, colors)
     }
+
+    /// Add a command to update the LED at `(row, col)` in a matrix zone of a controller.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the controller is not found in this group,
+    /// or see [`Command::set_matrix_pixel()`].
+    pub fn set_controller_matrix_pixel<C: Into<Color>>(
+        &mut self,
+        controller_id: impl ControllerIndex,
+        zone_id: usize,
+        row: usize,
+        col: usize,
+        color: C,
+    ) -> OpenRgbResult<()> {
+        let cmd = self.get_cmd_mut(controller_id)?;
+        cmd.set_matrix_pixel(zone_id, row, col, color)
+    }
+
+    /// Add a command to draw an image onto a matrix zone of a controller.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the controller is not found in this group,
+    /// or see [`Command::blit_matrix()`].
+    pub fn blit_controller_matrix(
+        &mut self,
+        controller_id: impl ControllerIndex,
+        zone_id: usize,
+        width: usize,
+        height: usize,
+        pixels: &[Color],
+    ) -> OpenRgbResult<()> {
+        let cmd = self.get_cmd_mut(controller_id)?;
+        cmd.blit_matrix(zone_id, width, height, pixels)
+    }
 }
 
 /// A command to update the LEDs in a controller.
@@ -277,6 +313,60 @@ impl<'a> Command<'a> {
         })
     }
 
+    /// Adds a command to update the LED at `(row, col)` in a matrix zone of this controller.
+    ///
+    /// Writing to a position without an LED does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the zone is not a matrix zone or `(row, col)` is out of bounds.
+    pub fn set_matrix_pixel<C: Into<Color>>(
+        &mut self,
+        zone_id: usize,
+        row: usize,
+        col: usize,
+        color: C,
+    ) -> OpenRgbResult<()> {
+        let zone = self.controller.get_zone(zone_id)?;
+        let matrix = matrix_of(&zone)?;
+        if row >= matrix.height() || col >= matrix.width() {
+            return Err(OpenRgbError::CommandError(format!(
+                "Position ({row}, {col}) is out of bounds for {}x{} matrix of zone {}",
+                matrix.width(),
+                matrix.height(),
+                zone.name()
+            )));
+        }
+        match matrix.led_index(row, col) {
+            Some(idx) => self.set_colors(zone.offset() + idx, &[color.into()]),
+            None => Ok(()),
+        }
+    }
+
+    /// Adds a command to draw a `width` by `height` image onto a matrix zone of this controller.
+    ///
+    /// `pixels` are in row major order, the image is scaled to the size of the matrix
+    /// using nearest neighbour sampling. Positions without an LED are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the zone is not a matrix zone
+    /// or `pixels` does not contain exactly `width * height` colors.
+    pub fn blit_matrix(
+        &mut self,
+        zone_id: usize,
+        width: usize,
+        height: usize,
+        pixels: &[Color],
+    ) -> OpenRgbResult<()> {
+        let zone = self.controller.get_zone(zone_id)?;
+        let offset = zone.offset();
+        for (idx, color) in matrix_of(&zone)?.sample_image(width, height, pixels)? {
+            self.set_colors(offset + idx, &[color])?;
+        }
+        Ok(())
+    }
+
     /// Adds an `UpdateCommand` to this command.
     ///
     /// # Errors
@@ -367,3 +457,10 @@ impl<'a> Command<'a> {
         Ok(())
     }
 }
+
+/// Returns the matrix of `zone`, or an error if it is not a matrix zone.
+fn matrix_of<'c>(zone: &Zone<'c>) -> OpenRgbResult<ZoneMatrix<'c>> {
+    zone.matrix().ok_or_else(|| {
+        OpenRgbError::CommandError(format!("Zone {} is not a matrix zone", zone.name()))
+    })
+}
@@ -1,7 +1,17 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use flagset::FlagSet;
+
 use crate::{
-    ControllerMode, ControllerModeKind, DeviceType, Led, LedData, OpenRgbError, OpenRgbResult,
-    ZoneData,
-    client::command::Command,
+    ControllerCalibration, ControllerCapabilities, ControllerFlags, ControllerMode,
+    ControllerModeKind, DeviceId, DeviceType, KeyCode, KeyGroup, Led, LedData, OpenRgbError,
+    OpenRgbResult, PowerLimit, SegmentManager, SegmentState, ZoneData, ZoneResize,
+    client::{
+        brightness::{Brightness, scale_colors},
+        command::Command,
+        keymap,
+        segment_manager::{prune_zone_segments, segment_states},
+    },
     data::{ModeData, ModeFlag},
     protocol::{
         OpenRgbProtocol,
@@ -16,10 +26,29 @@ use super::Zone;
 /// # Example
 ///
 /// see `examples/controller.rs` for example usage
+///
+/// Cloning a controller is cheap enough for building groups,
+/// clones share the connection and master brightness with the original.
+#[derive(Clone)]
 pub struct Controller {
     id: usize,
     proto: OpenRgbProtocol,
     data: ControllerData,
+    calibration: Option<ControllerCalibration>,
+    master_brightness: Brightness,
+    brightness: Brightness,
+    power_limit: Option<PowerLimit>,
+    /// Colors last written to each LED by this client.
+    sent: Arc<Mutex<Vec<Option<SentColor>>>>,
+}
+
+/// A color written to an LED by this client.
+#[derive(Debug, Clone, Copy)]
+struct SentColor {
+    /// Color as given to the controller, before brightness, calibration and power limit.
+    requested: Color,
+    /// Color as sent to the server.
+    output: Color,
 }
 
 impl PartialEq for Controller {
@@ -42,14 +71,33 @@ impl std::fmt::Debug for Controller {
 }
 
 impl Controller {
-    pub(crate) fn new(id: usize, proto: OpenRgbProtocol, data: ControllerData) -> Self {
-        Self { id, proto, data }
+    pub(crate) fn new(
+        id: usize,
+        proto: OpenRgbProtocol,
+        data: ControllerData,
+        master_brightness: Brightness,
+    ) -> Self {
+        let sent = vec![None; data.colors().len()];
+        Self {
+            id,
+            proto,
+            data,
+            calibration: None,
+            master_brightness,
+            brightness: Brightness::default(),
+            power_limit: None,
+            sent: Arc::new(Mutex::new(sent)),
+        }
     }
 
     pub(crate) fn proto(&self) -> &OpenRgbProtocol {
         &self.proto
     }
 
+    pub(crate) fn data(&self) -> &ControllerData {
+        &self.data
+    }
+
     /// Returns the ID of this controller.
     pub fn id(&self) -> usize {
         self.id
@@ -89,9 +137,34 @@ impl Controller {
 
             #[call(leds)]
             pub(crate) fn led_data(&self) -> &[LedData];
+
+            /// Returns the alternative names of the LEDs in this controller.
+            ///
+            /// Returns `None` if the protocol version is lower than 5.
+            pub fn led_alt_names(&self) -> Option<&[String]>;
+            /// Returns the flags of this controller.
+            ///
+            /// Returns `None` if the protocol version is lower than 5.
+            pub fn flags(&self) -> Option<FlagSet<ControllerFlags>>;
         }
     }
 
+    /// Returns true if this is a virtual device.
+    ///
+    /// Returns false if the protocol version is lower than 5.
+    pub fn is_virtual(&self) -> bool {
+        self.flags()
+            .is_some_and(|f| f.contains(ControllerFlags::IsVirtual))
+    }
+
+    /// Returns true if this controller is on a remote `OpenRGB` instance.
+    ///
+    /// Returns false if the protocol version is lower than 5.
+    pub fn is_remote(&self) -> bool {
+        self.flags()
+            .is_some_and(|f| f.contains(ControllerFlags::IsRemote))
+    }
+
     /// Initialises a controller by setting it to a controllable mode.
     /// This function also changes the LEDs to a rainbow, so you can see if it worked.
     pub async fn init(&self) -> OpenRgbResult<()> {
@@ -131,6 +204,16 @@ impl Controller {
             .map(|m| ControllerMode::new(m, active_mode.id() == m.id()))
     }
 
+    /// Returns the identity of the device, which unlike [`Self::id()`] stays the same across rescans.
+    pub fn device_id(&self) -> DeviceId {
+        DeviceId::from(self)
+    }
+
+    /// Returns a summary of this controller and what each of its modes supports.
+    pub fn capabilities(&self) -> ControllerCapabilities {
+        ControllerCapabilities::new(self)
+    }
+
     /// Sets this controller to a controllable mode.
     pub async fn set_controllable_mode(&self) -> OpenRgbResult<()> {
         let mode = self
@@ -168,13 +251,205 @@ impl Controller {
         self.zones().iter().map(|z| Zone::new(self, z))
     }
 
+    /// Returns the calibration of this controller, if one is set.
+    pub fn calibration(&self) -> Option<&ControllerCalibration> {
+        self.calibration.as_ref()
+    }
+
+    /// Sets the calibration of this controller, or removes it when `None` is given.
+    ///
+    /// The calibration is applied to every color written to this controller,
+    /// this includes writes done through [`Command`], [`Zone`] and [`crate::Segment`].
+    pub fn set_calibration(&mut self, calibration: Option<ControllerCalibration>) {
+        self.calibration = calibration;
+    }
+
+    /// Returns the brightness of this controller, between 0.0 and 1.0.
+    ///
+    /// This does not include the master brightness of the client.
+    pub fn brightness(&self) -> f32 {
+        self.brightness.get()
+    }
+
+    /// Sets the brightness of this controller, clamped to `0.0..=1.0`.
+    ///
+    /// Every color written to this controller is scaled by this brightness
+    /// and the master brightness set using [`crate::OpenRgbClient::set_brightness()`].
+    /// This does not change the brightness setting of the active mode.
+    pub fn set_brightness(&self, brightness: f32) {
+        self.brightness.set(brightness);
+    }
+
+    /// Returns the power limit of this controller, if one is set.
+    pub fn power_limit(&self) -> Option<PowerLimit> {
+        self.power_limit
+    }
+
+    /// Sets the power limit of this controller, or removes it when `None` is given.
+    pub fn set_power_limit(&mut self, power_limit: Option<PowerLimit>) {
+        self.power_limit = power_limit;
+    }
+
+    /// Prepares the `requested` colors to be sent, where `requested[0]` is the LED at `offset`.
+    ///
+    /// Applies brightness, calibration and the power limit, in that order.
+    /// `None` entries keep the color reported by the server, which is sent unchanged,
+    /// as it was already prepared when it was written.
+    /// The power limit is evaluated against the whole controller frame,
+    /// using the colors last sent to the other LEDs, and only scales the requested colors.
+    fn prepare_colors(&self, offset: usize, requested: &[Option<Color>]) -> Vec<Color> {
+        let mut colors = requested
+            .iter()
+            .map(|c| c.unwrap_or_default())
+            .collect::<Vec<_>>();
+        scale_colors(
+            &mut colors,
+            self.master_brightness.get() * self.brightness.get(),
+        );
+        self.calibrate(offset, &mut colors);
+        let server = self.colors();
+        for (idx, (color, requested)) in colors.iter_mut().zip(requested).enumerate() {
+            if requested.is_none() {
+                *color = server.get(offset + idx).copied().unwrap_or_default();
+            }
+        }
+
+        if let Some(limit) = &self.power_limit {
+            let mut others = self.output_frame();
+            let end = (offset + colors.len()).min(others.len());
+            if offset < end {
+                others.drain(offset..end);
+            }
+            let mut written = Vec::with_capacity(colors.len());
+            for (color, requested) in colors.iter().zip(requested) {
+                match requested {
+                    Some(_) => written.push(*color),
+                    None => others.push(*color),
+                }
+            }
+            let factor = limit.scale_factor_in_frame(&others, &written);
+            for (color, requested) in colors.iter_mut().zip(requested) {
+                if requested.is_some() {
+                    scale_colors(std::slice::from_mut(color), factor);
+                }
+            }
+        }
+        colors
+    }
+
+    /// Returns the color the server shows for every LED as far as this client knows,
+    /// after brightness, calibration and the power limit.
+    fn output_frame(&self) -> Vec<Color> {
+        let sent = self.sent();
+        let colors = self
+            .colors()
+            .iter()
+            .enumerate()
+            .map(|(idx, color)| {
+                sent.get(idx)
+                    .copied()
+                    .flatten()
+                    .map_or(*color, |s| s.output)
+            })
+            .collect();
+        drop(sent);
+        colors
+    }
+
+    /// Applies the calibration to `colors`, where `colors[0]` is the LED at `offset`.
+    fn calibrate(&self, offset: usize, colors: &mut [Color]) {
+        let Some(calibration) = &self.calibration else {
+            return;
+        };
+        let end = offset + colors.len();
+        let mut zone_start = 0;
+        for zone in self.zones() {
+            let zone_end = zone_start + zone.leds_count as usize;
+            let (start, stop) = (zone_start.max(offset), zone_end.min(end));
+            if start < stop {
+                calibration
+                    .for_zone(&zone.name)
+                    .apply_all(&mut colors[start - offset..stop - offset]);
+            }
+            zone_start = zone_end;
+        }
+        // LEDs that are not part of any zone
+        if zone_start < end {
+            let start = zone_start.max(offset);
+            calibration.default.apply_all(&mut colors[start - offset..]);
+        }
+    }
+
+    /// Returns the color of every LED as far as this client knows.
+    ///
+    /// This is the color last written to the LED through this controller (or a clone of it),
+    /// before brightness, calibration and the power limit are applied.
+    /// LEDs that were not written since the last [`Self::sync_controller_data()`] use the colors
+    /// reported by the server. Those already include any brightness or calibration that was applied
+    /// when they were written, so writes that merge them (e.g. [`crate::Segment::set_leds()`]) send them unchanged.
+    pub fn known_colors(&self) -> Vec<Color> {
+        let sent = self.sent();
+        let colors = self
+            .colors()
+            .iter()
+            .enumerate()
+            .map(|(idx, color)| {
+                sent.get(idx)
+                    .copied()
+                    .flatten()
+                    .map_or(*color, |s| s.requested)
+            })
+            .collect();
+        drop(sent);
+        colors
+    }
+
+    /// Records `requested` colors as written, with the `output` colors that were sent for them,
+    /// where `requested[0]` is the LED at `offset`. `None` entries are not recorded.
+    fn record_sent(&self, offset: usize, requested: &[Option<Color>], output: &[Color]) {
+        let mut sent = self.sent();
+        let end = (offset + requested.len()).min(sent.len());
+        if offset < end {
+            for (slot, (requested, &output)) in sent[offset..end]
+                .iter_mut()
+                .zip(requested.iter().zip(output))
+            {
+                if let Some(requested) = *requested {
+                    *slot = Some(SentColor { requested, output });
+                }
+            }
+        }
+        drop(sent);
+    }
+
+    /// Returns the colors last written by this client to the `len` LEDs starting at `offset`,
+    /// `None` for LEDs that were not written yet.
+    fn requested_colors(&self, offset: usize, len: usize) -> Vec<Option<Color>> {
+        let sent = self.sent();
+        let colors = (offset..offset + len)
+            .map(|idx| sent.get(idx).copied().flatten().map(|s| s.requested))
+            .collect();
+        drop(sent);
+        colors
+    }
+
+    fn sent(&self) -> MutexGuard<'_, Vec<Option<SentColor>>> {
+        self.sent
+            .lock()
+            .expect("Controller color cache lock poisoned")
+    }
+
     /// Sets a single LED to the given `color`.
     ///
     /// When doing many writes in rapid succession, it is recommended to use the [`Self::cmd()`] method instead.
     pub async fn set_led<C: Into<Color>>(&self, led: usize, color: C) -> OpenRgbResult<()> {
+        let requested = [Some(color.into())];
+        let color = self.prepare_colors(led, &requested);
         self.proto
-            .update_led(self.id as u32, led as i32, &color.into())
-            .await
+            .update_led(self.id as u32, led as i32, &color[0])
+            .await?;
+        self.record_sent(led, &requested, &color);
+        Ok(())
     }
 
     /// Sets all LEDs of this controller to a given `color`.
@@ -190,20 +465,73 @@ impl Controller {
         &self,
         colors: impl IntoIterator<Item = C>,
     ) -> OpenRgbResult<()> {
-        let color_v = colors.into_iter().map(Into::into).collect::<Vec<_>>();
-        self.proto.update_leds(self.id as u32, &color_v).await
+        let requested = colors
+            .into_iter()
+            .map(|c| Some(c.into()))
+            .collect::<Vec<_>>();
+        let color_v = self.prepare_colors(0, &requested);
+        self.proto.update_leds(self.id as u32, &color_v).await?;
+        self.record_sent(0, &requested, &color_v);
+        Ok(())
     }
 
     /// Sets the LEDs of a specific zone to the given `colors`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the zone does not exist, or communication with the server fails.
     pub async fn set_zone_leds<C: Into<Color>>(
         &self,
         zone_id: usize,
         colors: impl IntoIterator<Item = C>,
     ) -> OpenRgbResult<()> {
-        let color_v = colors.into_iter().map(Into::into).collect::<Vec<_>>();
+        let requested = colors
+            .into_iter()
+            .map(|c| Some(c.into()))
+            .collect::<Vec<_>>();
+        let offset = self.get_zone_led_offset(zone_id)?;
+        self.write_zone(zone_id, offset, &requested).await
+    }
+
+    /// Sets the LEDs of zone `zone_id` starting at index `start` in the zone to `colors`,
+    /// and sends the other LEDs of the zone with their known color in the same update.
+    ///
+    /// LEDs that were not written by this client are sent with the color reported by the server.
+    pub(crate) async fn set_zone_range(
+        &self,
+        zone_id: usize,
+        start: usize,
+        colors: &[Color],
+    ) -> OpenRgbResult<()> {
+        let zone = self.get_zone(zone_id)?;
+        let (offset, num_leds) = (zone.offset(), zone.num_leds());
+        if start + colors.len() > num_leds {
+            return Err(OpenRgbError::CommandError(format!(
+                "{} colors starting at LED {start} are out of bounds for zone {} with {num_leds} LEDs",
+                colors.len(),
+                zone.name(),
+            )));
+        }
+        let mut requested = self.requested_colors(offset, num_leds);
+        for (slot, color) in requested[start..].iter_mut().zip(colors) {
+            *slot = Some(*color);
+        }
+        self.write_zone(zone_id, offset, &requested).await
+    }
+
+    /// Prepares and sends `requested` to zone `zone_id` starting at controller LED `offset`.
+    async fn write_zone(
+        &self,
+        zone_id: usize,
+        offset: usize,
+        requested: &[Option<Color>],
+    ) -> OpenRgbResult<()> {
+        let color_v = self.prepare_colors(offset, requested);
         self.proto
             .update_zone_leds(self.id as u32, zone_id as u32, &color_v)
-            .await
+            .await?;
+        self.record_sent(offset, requested, &color_v);
+        Ok(())
     }
 
     /// Turns off all LEDs of this controller.
@@ -221,6 +549,46 @@ impl Controller {
             .map(move |(id, _)| Led::new(id, self))
     }
 
+    /// Returns the LED with the given name or alternative name.
+    ///
+    /// Names are compared case insensitively and the `"Key: "` prefix of keyboard LEDs is optional.
+    /// If no LED has this exact name, but `name` is a known key (see [`KeyCode::from_name()`]),
+    /// the LED for that key is returned instead.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use openrgb2::{Color, OpenRgbClient, OpenRgbResult};
+    /// # async fn example() -> OpenRgbResult<()> {
+    /// # let client = OpenRgbClient::connect().await?;
+    /// let keyboard = client.get_controller(0).await?;
+    /// if let Some(esc) = keyboard.find_led("Escape") {
+    ///     esc.set_led(Color::new(255, 0, 0)).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_led(&self, name: &str) -> Option<Led<'_>> {
+        let normalised = keymap::normalise(name);
+        self.led_iter()
+            .find(|led| led.names().any(|n| keymap::normalise(n) == normalised))
+            .or_else(|| self.find_key(KeyCode::from_name(name)?))
+    }
+
+    /// Returns the LED of the given keyboard key.
+    pub fn find_key(&self, key: KeyCode) -> Option<Led<'_>> {
+        self.led_iter().find(|led| led.key_code() == Some(key))
+    }
+
+    /// Returns the LEDs of all keys in `group` that this controller has, in the order of [`KeyGroup::keys()`].
+    pub fn find_key_group(&self, group: KeyGroup) -> Vec<Led<'_>> {
+        group
+            .keys()
+            .iter()
+            .filter_map(|key| self.find_key(*key))
+            .collect()
+    }
+
     /// Creates a [`Command`] for this controller.
     ///
     /// Controller LEDs can be updated in three ways:
@@ -295,8 +663,13 @@ impl Controller {
     /// Fetches controller data again. This updates the state of the controller data.
     ///
     /// Currently this has to be called manually.
+    /// Afterwards [`Self::known_colors()`] returns the colors reported by the server,
+    /// as writes by other clients or a [`crate::ControllerState::restore()`] are not known to this controller.
     pub async fn sync_controller_data(&mut self) -> OpenRgbResult<()> {
         let data = self.proto.get_controller(self.id as u32).await?;
+        // the server colors are authoritative again, they may have been written
+        // by a restore, another client, or the LEDs moved
+        *self.sent() = vec![None; data.colors().len()];
         self.data = data;
         Ok(())
     }
@@ -306,6 +679,7 @@ impl Controller {
     /// # Important
     ///
     /// Using this frequently can cause wear on the flash memory, use this sparingly.
+    /// [`crate::SaveGuard`] can be used to skip redundant saves and limit how often a controller is saved.
     pub async fn save_mode(&self) -> OpenRgbResult<()> {
         let active_mode = self.active_mode();
         if !active_mode.flags().contains(ModeFlag::ManualSave) {
@@ -324,6 +698,161 @@ impl Controller {
     pub async fn clear_segments(&self) -> OpenRgbResult<()> {
         self.proto.clear_segments(self.id as u32).await
     }
+
+    /// Returns a [`SegmentManager`] to edit the segments of this controller.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the protocol version is lower than 5.
+    pub fn segment_manager(&mut self) -> OpenRgbResult<SegmentManager<'_>> {
+        SegmentManager::new(self)
+    }
+
+    /// Resizes the zone with id `zone_id` to `new_size` LEDs, then resyncs and verifies the controller data.
+    ///
+    /// Segments of the zone that no longer fit are removed.
+    /// As this borrows the controller mutably, no [`Zone`], [`crate::Segment`] or [`Command`]
+    /// can refer to the old size afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the zone is not resizable, `new_size` is out of range for the zone,
+    /// the zone does not have `new_size` LEDs after resizing, or communication with the server fails.
+    pub async fn resize_zone(
+        &mut self,
+        zone_id: usize,
+        new_size: usize,
+    ) -> OpenRgbResult<ZoneResize> {
+        let segments = self.capture_segments();
+        let from = self.resize_and_verify(zone_id, new_size).await?;
+        let removed_segments = self.restore_segments(zone_id, segments).await?;
+        Ok(ZoneResize {
+            zone_id,
+            from,
+            to: new_size,
+            removed_segments,
+        })
+    }
+
+    /// Resizes the zone with id `zone_id`, resyncs and checks the new size, without touching segments.
+    ///
+    /// Returns the size of the zone before resizing.
+    async fn resize_and_verify(&mut self, zone_id: usize, new_size: usize) -> OpenRgbResult<usize> {
+        let zone = self.get_zone(zone_id)?;
+        let from = zone.num_leds();
+        zone.resize(new_size).await?;
+        self.sync_controller_data().await?;
+
+        let to = self.get_zone(zone_id)?.num_leds();
+        if to != new_size {
+            return Err(OpenRgbError::CommandError(format!(
+                "Zone {zone_id} of {} has {to} LEDs after resizing to {new_size}",
+                self.name()
+            )));
+        }
+        Ok(from)
+    }
+
+    /// Returns the segments of every zone, or `None` if the protocol does not support segment commands.
+    fn capture_segments(&self) -> Option<Vec<Vec<SegmentState>>> {
+        (self.proto.get_protocol_version() >= 5).then(|| segment_states(self))
+    }
+
+    /// Writes back `segments` captured by [`Self::capture_segments()`],
+    /// without the segments of zone `zone_id` that no longer fit in it.
+    ///
+    /// Returns the removed segments.
+    async fn restore_segments(
+        &mut self,
+        zone_id: usize,
+        segments: Option<Vec<Vec<SegmentState>>>,
+    ) -> OpenRgbResult<Vec<SegmentState>> {
+        let Some(mut segments) = segments else {
+            return Ok(Vec::new());
+        };
+        let zone_leds = self.get_zone(zone_id)?.num_leds();
+        let removed = prune_zone_segments(&mut segments, zone_id, zone_leds);
+        let mut manager = self.segment_manager()?;
+        manager.set_all(segments);
+        manager.apply().await?;
+        Ok(removed)
+    }
+
+    /// Helps to find the number of LEDs of an addressable strip by lighting the last LED for each size.
+    ///
+    /// For every size in `sizes` the zone is resized, all LEDs are turned off except the last one,
+    /// which is set to `color`, and `confirm` is called with the size.
+    /// `confirm` should return true once the last LED of the strip lights up,
+    /// for example after asking the user. The zone is left at that size and the size is returned.
+    ///
+    /// If no size is confirmed, or an error occurs, the zone is resized back to its original size
+    /// and `None` is returned. The segments of the controller are restored afterwards,
+    /// only segments that do not fit in the confirmed size are removed.
+    /// The controller should be in a mode that allows setting LEDs, see [`Self::set_controllable_mode()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if resizing fails, see [`Self::resize_zone()`].
+    pub async fn detect_zone_size<F, Fut>(
+        &mut self,
+        zone_id: usize,
+        sizes: impl IntoIterator<Item = usize>,
+        color: impl Into<Color>,
+        mut confirm: F,
+    ) -> OpenRgbResult<Option<usize>>
+    where
+        F: FnMut(usize) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        let color = color.into();
+        let original = self.get_zone(zone_id)?.num_leds();
+        // segments are restored once at the end, so probing small sizes does not remove them
+        let segments = self.capture_segments();
+        let probed = async {
+            for size in sizes {
+                if size == 0 {
+                    continue;
+                }
+                self.resize_and_verify(zone_id, size).await?;
+                let mut colors = vec![Color::default(); size];
+                colors[size - 1] = color;
+                self.set_zone_leds(zone_id, colors).await?;
+                if confirm(size).await {
+                    return Ok(Some(size));
+                }
+            }
+            Ok(None)
+        }
+        .await;
+        match probed {
+            Ok(Some(size)) => {
+                self.restore_segments(zone_id, segments).await?;
+                Ok(Some(size))
+            }
+            Ok(None) => {
+                self.restore_zone(zone_id, original, segments).await?;
+                Ok(None)
+            }
+            Err(err) => {
+                if let Err(restore_err) = self.restore_zone(zone_id, original, segments).await {
+                    tracing::warn!("Failed to restore zone {zone_id} after error: {restore_err}");
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Resizes zone `zone_id` back to `size` and writes back `segments`.
+    async fn restore_zone(
+        &mut self,
+        zone_id: usize,
+        size: usize,
+        segments: Option<Vec<Vec<SegmentState>>>,
+    ) -> OpenRgbResult<()> {
+        self.resize_and_verify(zone_id, size).await?;
+        self.restore_segments(zone_id, segments).await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -358,4 +887,69 @@ mod tests {
         cmd.execute().await?;
         Ok(())
     }
+
+    #[tokio::test]
+    #[ignore = "can only test with openrgb running"]
+    async fn test_segment_keeps_zone_colors() -> OpenRgbResult<()> {
+        let client = OpenRgbClient::connect().await?;
+        let controller = client.get_controller(0).await?;
+        controller.set_controllable_mode().await?;
+        let (red, green) = (Color::new(255, 0, 0), Color::new(0, 255, 0));
+        let zone = controller.get_zone(0)?;
+        zone.set_all_leds(red).await?;
+        let segment = zone.get_segment(0)?;
+        segment.set_all_leds(green).await?;
+
+        let mut expected = vec![red; zone.num_leds()];
+        expected[segment.offset()..segment.offset() + segment.num_leds()].fill(green);
+        assert_eq!(zone.known_colors(), expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "can only test with openrgb running"]
+    async fn test_segment_keeps_unknown_colors() -> OpenRgbResult<()> {
+        let client = OpenRgbClient::connect().await?;
+        let controller = client.get_controller(0).await?;
+        controller.set_controllable_mode().await?;
+        controller.set_brightness(0.5);
+        controller.get_zone(0)?.set_all_leds([200, 100, 50]).await?;
+
+        // a fresh controller only knows the colors reported by the server
+        let mut fresh = client.get_controller(0).await?;
+        fresh.set_brightness(0.5);
+        let zone_colors = fresh.get_zone(0)?.known_colors();
+        let zone = fresh.get_zone(0)?;
+        let segment = zone.get_segment(0)?;
+        let range = segment.offset()..segment.offset() + segment.num_leds();
+        segment.set_all_leds([0, 255, 0]).await?;
+        fresh.sync_controller_data().await?;
+
+        // LEDs outside the segment were not recorded, so these are the synced server colors
+        let synced = fresh.get_zone(0)?.known_colors();
+        for (idx, (synced, before)) in synced.iter().zip(&zone_colors).enumerate() {
+            if !range.contains(&idx) {
+                assert_eq!(synced, before, "LED {idx} changed");
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "can only test with openrgb running"]
+    async fn test_resize_zone() -> OpenRgbResult<()> {
+        let client = OpenRgbClient::connect().await?;
+        let mut controller = client.get_controller(0).await?;
+        let Some(zone) = controller.get_all_zones().find(Zone::is_resizable) else {
+            return Ok(());
+        };
+        let (zone_id, original, max) = (zone.zone_id(), zone.num_leds(), zone.leds_max());
+        assert!(controller.resize_zone(zone_id, max + 1).await.is_err());
+
+        let resize = controller.resize_zone(zone_id, max).await?;
+        assert_eq!((resize.from, resize.to), (original, max));
+        assert_eq!(controller.get_zone(zone_id)?.num_leds(), max);
+        controller.resize_zone(zone_id, original).await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,235 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Controller, data::DeviceType};
+
+/// Identity of a device that stays the same across rescans.
+///
+/// [`Controller::id()`] is the index of the controller on the server,
+/// which changes when devices are rescanned, plugged in or removed.
+/// A `DeviceId` identifies the device itself, so configuration can be stored
+/// and applied to the right controller later, see [`crate::ControllerGroup::find()`].
+///
+/// With the `serde` feature, `vendor` and `device_type` can be left out,
+/// which makes them match any controller.
+///
+/// # Example
+///
+/// ```no_run
+/// # use openrgb2::*;
+/// # async fn example() -> OpenRgbResult<()> {
+/// let client = OpenRgbClient::connect().await?;
+/// let keyboard = client.get_controllers_of_type(DeviceType::Keyboard).await?.into_first()?;
+/// let id = keyboard.device_id();
+///
+/// client.rescan_devices().await?;
+/// let controllers = client.get_all_controllers().await?;
+/// if let Some(keyboard) = controllers.find(&id) {
+///     println!("keyboard is now controller {}", keyboard.id());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeviceId {
+    /// Vendor of the device, empty matches any vendor.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub vendor: String,
+    /// Name of the device.
+    pub name: String,
+    /// Serial of the device, many devices report an empty serial.
+    pub serial: String,
+    /// Location of the device, such as a USB path or I2C address.
+    pub location: String,
+    /// Type of the device, [`DeviceType::Unknown`] matches any type.
+    #[cfg_attr(feature = "serde", serde(default = "unknown_device_type"))]
+    pub device_type: DeviceType,
+}
+
+#[cfg(feature = "serde")]
+fn unknown_device_type() -> DeviceType {
+    DeviceType::Unknown
+}
+
+/// How well a [`DeviceId`] matches a controller, ordered from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DeviceMatch {
+    /// Serial and location are the same.
+    Exact,
+    /// The serial is the same, the device moved to another location.
+    Serial,
+    /// No serial to compare, the location is the same.
+    Location,
+    /// No serial to compare and the location changed, only the name is the same.
+    ///
+    /// These matches are only used if they are unambiguous.
+    Name,
+}
+
+impl From<&Controller> for DeviceId {
+    fn from(controller: &Controller) -> Self {
+        Self {
+            vendor: controller.vendor().to_owned(),
+            name: controller.name().to_owned(),
+            serial: controller.serial().to_owned(),
+            location: controller.location().to_owned(),
+            device_type: controller.device_type(),
+        }
+    }
+}
+
+impl std::fmt::Display for DeviceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        match (self.serial.is_empty(), self.location.is_empty()) {
+            (false, _) => write!(f, " ({})", self.serial),
+            (true, false) => write!(f, " ({})", self.location),
+            (true, true) => Ok(()),
+        }
+    }
+}
+
+impl DeviceId {
+    /// Returns how well this id matches `other`, or `None` if they are different devices.
+    ///
+    /// Name, vendor and device type must be the same.
+    /// If both ids have a serial, the serials must be the same and the location may differ.
+    /// Without a serial, ids with the same location are preferred,
+    /// but a device that moved is still matched by name, see [`DeviceMatch::Name`].
+    pub fn match_quality(&self, other: &Self) -> Option<DeviceMatch> {
+        let same_vendor =
+            self.vendor.is_empty() || other.vendor.is_empty() || self.vendor == other.vendor;
+        let same_type = self.device_type == DeviceType::Unknown
+            || other.device_type == DeviceType::Unknown
+            || self.device_type == other.device_type;
+        if self.name != other.name || !same_vendor || !same_type {
+            return None;
+        }
+        let same_location = self.location == other.location;
+        match (
+            self.serial.is_empty() || other.serial.is_empty(),
+            same_location,
+        ) {
+            (false, _) if self.serial != other.serial => None,
+            (false, true) => Some(DeviceMatch::Exact),
+            (false, false) => Some(DeviceMatch::Serial),
+            (true, true) => Some(DeviceMatch::Location),
+            (true, false) => Some(DeviceMatch::Name),
+        }
+    }
+
+    /// Returns how well this id matches `controller`, see [`Self::match_quality()`].
+    pub fn matches(&self, controller: &Controller) -> Option<DeviceMatch> {
+        self.match_quality(&controller.device_id())
+    }
+}
+
+/// Assigns each of `ids` to at most one of `candidates`, best matches first.
+///
+/// [`DeviceMatch::Name`] matches are only assigned if both the id and the candidate
+/// have exactly one remaining name match.
+pub(crate) fn assign(ids: &[&DeviceId], candidates: &[DeviceId]) -> Vec<Option<usize>> {
+    let mut pairs = ids
+        .iter()
+        .enumerate()
+        .flat_map(|(i, id)| {
+            candidates
+                .iter()
+                .enumerate()
+                .filter_map(move |(c, candidate)| Some((id.match_quality(candidate)?, i, c)))
+        })
+        .collect::<Vec<_>>();
+    pairs.sort_unstable();
+
+    let mut assigned = vec![None; ids.len()];
+    let mut taken = vec![false; candidates.len()];
+    for &(quality, i, c) in &pairs {
+        if assigned[i].is_some() || taken[c] {
+            continue;
+        }
+        if quality == DeviceMatch::Name {
+            let open = |&&(q, i2, c2): &&(DeviceMatch, usize, usize)| {
+                q == DeviceMatch::Name && assigned[i2].is_none() && !taken[c2]
+            };
+            let for_id = pairs.iter().filter(open).filter(|p| p.1 == i).count();
+            let for_candidate = pairs.iter().filter(open).filter(|p| p.2 == c).count();
+            if for_id > 1 || for_candidate > 1 {
+                continue;
+            }
+        }
+        assigned[i] = Some(c);
+        taken[c] = true;
+    }
+    assigned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(name: &str, serial: &str, location: &str) -> DeviceId {
+        DeviceId {
+            vendor: "Corsair".to_owned(),
+            name: name.to_owned(),
+            serial: serial.to_owned(),
+            location: location.to_owned(),
+            device_type: DeviceType::DRam,
+        }
+    }
+
+    #[test]
+    fn test_match_quality() {
+        let a = id("Vengeance", "123", "I2C: 0x58");
+        assert_eq!(a.match_quality(&a), Some(DeviceMatch::Exact));
+        assert_eq!(
+            a.match_quality(&id("Vengeance", "123", "I2C: 0x59")),
+            Some(DeviceMatch::Serial)
+        );
+        assert_eq!(a.match_quality(&id("Vengeance", "456", "I2C: 0x58")), None);
+        assert_eq!(a.match_quality(&id("Dominator", "123", "I2C: 0x58")), None);
+        assert_eq!(
+            a.match_quality(&id("Vengeance", "", "I2C: 0x58")),
+            Some(DeviceMatch::Location)
+        );
+        assert_eq!(
+            a.match_quality(&id("Vengeance", "", "I2C: 0x59")),
+            Some(DeviceMatch::Name)
+        );
+
+        let mut any = id("Vengeance", "", "I2C: 0x58");
+        any.vendor.clear();
+        any.device_type = DeviceType::Unknown;
+        assert_eq!(a.match_quality(&any), Some(DeviceMatch::Location));
+        any.device_type = DeviceType::Gpu;
+        assert_eq!(a.match_quality(&any), None);
+    }
+
+    #[test]
+    fn test_assign() {
+        let sticks = [
+            id("Vengeance", "", "I2C: 0x58"),
+            id("Vengeance", "", "I2C: 0x59"),
+            id("Keyboard", "", "USB: 1-2"),
+        ];
+        // indices shifted, locations are the same
+        let candidates = [sticks[2].clone(), sticks[1].clone(), sticks[0].clone()];
+        let ids = sticks.iter().collect::<Vec<_>>();
+        assert_eq!(assign(&ids, &candidates), [Some(2), Some(1), Some(0)]);
+
+        // the keyboard moved to another port, it is the only one with that name
+        let moved = [id("Keyboard", "", "USB: 3-1"), sticks[0].clone()];
+        assert_eq!(assign(&ids, &moved), [Some(1), None, Some(0)]);
+
+        // both sticks moved, it is ambiguous which is which
+        let both_moved = [
+            id("Vengeance", "", "I2C: 0x50"),
+            id("Vengeance", "", "I2C: 0x51"),
+        ];
+        assert_eq!(assign(&ids, &both_moved), [None, None, None]);
+
+        // one stick is still at its location, the other one can only be the moved stick
+        let one_moved = [sticks[0].clone(), id("Vengeance", "", "I2C: 0x51")];
+        assert_eq!(assign(&ids, &one_moved), [Some(0), Some(1), None]);
+    }
+}
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
 use crate::{
-    Controller, OpenRgbError, OpenRgbResult, client::command::CommandGroup, data::DeviceType,
+    CalibrationConfig, CapabilityReport, Controller, ControllerMode, DeviceId, ModeReport,
+    ModeSpec, OpenRgbError, OpenRgbResult, PowerLimit, Selector, Zone, ZoneSegment,
+    client::{command::CommandGroup, device_id::assign, util::join_all},
+    data::DeviceType,
 };
 
 /// Trait for things that can index into a `ControllerGroup`.
@@ -80,6 +83,137 @@ impl ControllerGroup {
         &mut self.controllers
     }
 
+    /// Sets the calibration of every controller that has an entry in `config`.
+    ///
+    /// Controllers without an entry keep their current calibration.
+    pub fn apply_calibration(&mut self, config: &CalibrationConfig) {
+        for controller in &mut self.controllers {
+            if let Some(calibration) = config.get(controller) {
+                controller.set_calibration(Some(calibration.clone()));
+            }
+        }
+    }
+
+    /// Sets the brightness of every controller in this group.
+    ///
+    /// See [`Controller::set_brightness()`].
+    pub fn set_brightness(&self, brightness: f32) {
+        for controller in &self.controllers {
+            controller.set_brightness(brightness);
+        }
+    }
+
+    /// Sets the power limit of every controller in this group.
+    ///
+    /// The budget applies to each controller separately.
+    pub fn set_power_limit(&mut self, power_limit: Option<PowerLimit>) {
+        for controller in &mut self.controllers {
+            controller.set_power_limit(power_limit);
+        }
+    }
+
+    /// Returns the capabilities of all controllers in this group.
+    pub fn capabilities(&self) -> CapabilityReport {
+        CapabilityReport {
+            controllers: self.iter().map(Controller::capabilities).collect(),
+        }
+    }
+
+    /// Returns the controllers that have a mode named `name` (ignoring case) for which `f` returns true,
+    /// together with that mode.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use openrgb2::*;
+    /// # fn example(group: &ControllerGroup) {
+    /// // all controllers that can breathe with custom colors
+    /// for (controller, mode) in group.with_mode("Breathing", |m| m.supports_color_mode(ColorMode::ModeSpecific)) {
+    ///     println!("{}: {:?}", controller.name(), mode.colors_max());
+    /// }
+    /// # }
+    /// ```
+    pub fn with_mode<'a, F>(
+        &'a self,
+        name: &'a str,
+        f: F,
+    ) -> impl Iterator<Item = (&'a Controller, ControllerMode<'a>)>
+    where
+        F: Fn(&ControllerMode<'a>) -> bool + 'a,
+    {
+        self.iter().filter_map(move |c| {
+            c.mode_iter()
+                .find(|m| m.name().eq_ignore_ascii_case(name) && f(m))
+                .map(|m| (c, m))
+        })
+    }
+
+    /// Returns the controller in this group that is the device identified by `id`.
+    ///
+    /// Returns `None` if no controller matches, or if only the names match
+    /// and several controllers have that name.
+    pub fn find(&self, id: &DeviceId) -> Option<&Controller> {
+        self.resolve([id]).pop().flatten()
+    }
+
+    /// Resolves each of `ids` to a controller in this group.
+    ///
+    /// Use this after [`crate::OpenRgbClient::rescan_devices()`] to find the new controllers
+    /// for stored ids. Each controller is used at most once, better matches take precedence,
+    /// so identical devices without a serial keep their location.
+    /// The result has one entry per id, in the same order.
+    pub fn resolve<'a>(
+        &self,
+        ids: impl IntoIterator<Item = &'a DeviceId>,
+    ) -> Vec<Option<&Controller>> {
+        self.resolve_positions(ids)
+            .into_iter()
+            .map(|pos| pos.map(|pos| &self.controllers[pos]))
+            .collect()
+    }
+
+    /// Like [`Self::resolve()`], but returns the positions of the controllers in this group.
+    pub(crate) fn resolve_positions<'a>(
+        &self,
+        ids: impl IntoIterator<Item = &'a DeviceId>,
+    ) -> Vec<Option<usize>> {
+        let candidates = self.iter().map(Controller::device_id).collect::<Vec<_>>();
+        assign(&ids.into_iter().collect::<Vec<_>>(), &candidates)
+    }
+
+    /// Returns a group of the controllers that match `selector`.
+    ///
+    /// The controllers are cloned, see [`Selector`] for the syntax.
+    pub fn select(&self, selector: &Selector) -> Self {
+        Self::new(
+            self.iter()
+                .filter(|c| selector.matches_controller(c))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Returns the zones of all controllers in this group that match `selector`.
+    pub fn select_zones(&self, selector: &Selector) -> Vec<Zone<'_>> {
+        self.iter()
+            .flat_map(Controller::get_all_zones)
+            .filter(|zone| selector.matches_zone(zone))
+            .collect()
+    }
+
+    /// Returns the segments of all controllers in this group that match `selector`.
+    pub fn select_segments(&self, selector: &Selector) -> Vec<ZoneSegment<'_>> {
+        let mut segments = Vec::new();
+        for zone in self.iter().flat_map(Controller::get_all_zones) {
+            segments.extend(
+                zone.segment_iter()
+                    .filter(|s| selector.matches_segment(&zone, s))
+                    .map(|s| ZoneSegment::new(zone.clone(), s.segment_id())),
+            );
+        }
+        segments
+    }
+
     /// Returns true if this group has no controllers.
     pub fn is_empty(&self) -> bool {
         self.controllers.is_empty()
@@ -104,6 +238,32 @@ impl ControllerGroup {
         idx.index(self)
     }
 
+    /// Keeps only the controllers for which `f` returns true.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&Controller) -> bool,
+    {
+        self.controllers.retain(f);
+    }
+
+    /// Removes virtual controllers from this group.
+    ///
+    /// See [`Controller::is_virtual()`].
+    #[must_use]
+    pub fn without_virtual(mut self) -> Self {
+        self.retain(|c| !c.is_virtual());
+        self
+    }
+
+    /// Removes controllers that are on a remote `OpenRGB` instance from this group.
+    ///
+    /// See [`Controller::is_remote()`].
+    #[must_use]
+    pub fn without_remote(mut self) -> Self {
+        self.retain(|c| !c.is_remote());
+        self
+    }
+
     /// Splits the controllers in this group by their device type.
     /// Returns one group per device type.
     pub fn split_per_type(self) -> HashMap<DeviceType, ControllerGroup> {
@@ -159,6 +319,24 @@ impl ControllerGroup {
         Ok(())
     }
 
+    /// Applies `spec` to every controller in this group.
+    ///
+    /// The controllers are updated concurrently, but as they share one connection to the server,
+    /// their requests are still sent one at a time.
+    ///
+    /// Each controller is set to the mode that matches the spec best, see [`ModeSpec::find_mode()`].
+    /// Speed and brightness are mapped onto the range of each device.
+    /// Controllers without a matching mode are left unchanged.
+    ///
+    /// The returned report lists, per controller, which parts of the spec could not be applied
+    /// and any errors that occurred, use [`ModeReport::into_result()`] to turn errors into a `Result`.
+    pub async fn apply_mode(&self, spec: &ModeSpec) -> ModeReport {
+        let futures = self.iter().map(|c| spec.apply(c)).collect();
+        ModeReport {
+            results: join_all(futures).await,
+        }
+    }
+
     /// Turns off all LEDs in all controllers in this group.
     pub async fn turn_off_leds(&self) -> OpenRgbResult<()> {
         for controller in &self.controllers {
@@ -244,4 +422,28 @@ mod tests {
         }
         Ok(())
     }
+
+    #[tokio::test]
+    #[ignore = "can only test with openrgb running"]
+    async fn test_select() -> OpenRgbResult<()> {
+        let client = OpenRgbClient::connect().await?;
+        let group = client.get_all_controllers().await?;
+        for controller in &group {
+            let selector = Selector::device_type(controller.device_type());
+            assert!(group.select(&selector).iter().any(|c| c == controller));
+            for device in group
+                .resolve([&controller.device_id()])
+                .into_iter()
+                .flatten()
+            {
+                assert_eq!(device, controller);
+            }
+        }
+        let zones = group.select_zones(&"zone~\"\"".parse()?);
+        assert_eq!(
+            zones.len(),
+            group.iter().map(|c| c.get_all_zones().count()).sum()
+        );
+        Ok(())
+    }
 }
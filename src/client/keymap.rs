@@ -0,0 +1,482 @@
+/// A keyboard key, independent of how a specific controller names it.
+///
+/// `OpenRGB` names keyboard LEDs like `"Key: Escape"` or `"Key: Number Pad 7"`.
+/// [`KeyCode::from_name()`] maps those names (and common alternatives such as `"Esc"` or `"Ctrl"`)
+/// to a key code, so keys can be addressed by meaning instead of by LED index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[expect(missing_docs, reason = "variant names are the key names")]
+pub enum KeyCode {
+    Escape,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    PrintScreen,
+    ScrollLock,
+    Pause,
+
+    Backtick,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    Digit0,
+    Minus,
+    Equals,
+    Backspace,
+
+    Tab,
+    Q,
+    W,
+    E,
+    R,
+    T,
+    Y,
+    U,
+    I,
+    O,
+    P,
+    LeftBracket,
+    RightBracket,
+    /// Backslash above enter on ANSI layouts.
+    Backslash,
+
+    CapsLock,
+    A,
+    S,
+    D,
+    F,
+    G,
+    H,
+    J,
+    K,
+    L,
+    Semicolon,
+    Quote,
+    /// Hash key next to enter on ISO layouts.
+    Pound,
+    Enter,
+
+    LeftShift,
+    /// Backslash next to left shift on ISO layouts.
+    IsoBackslash,
+    Z,
+    X,
+    C,
+    V,
+    B,
+    N,
+    M,
+    Comma,
+    Period,
+    Slash,
+    RightShift,
+
+    LeftControl,
+    LeftWindows,
+    LeftAlt,
+    LeftFn,
+    Space,
+    RightAlt,
+    RightFn,
+    Menu,
+    RightWindows,
+    RightControl,
+
+    Insert,
+    Home,
+    PageUp,
+    Delete,
+    End,
+    PageDown,
+    UpArrow,
+    LeftArrow,
+    DownArrow,
+    RightArrow,
+
+    NumLock,
+    NumpadDivide,
+    NumpadMultiply,
+    NumpadMinus,
+    NumpadPlus,
+    NumpadEnter,
+    NumpadDecimal,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+
+    MediaPlayPause,
+    MediaPrevious,
+    MediaNext,
+    MediaStop,
+    MediaMute,
+    VolumeUp,
+    VolumeDown,
+}
+
+/// Known key names, the first name of each key is the name `OpenRGB` uses.
+///
+/// Alternative names are lowercase, since names are compared after [`normalise()`].
+const KEY_NAMES: &[(KeyCode, &[&str])] = {
+    use KeyCode::*;
+    &[
+        (Escape, &["Escape", "esc"]),
+        (F1, &["F1"]),
+        (F2, &["F2"]),
+        (F3, &["F3"]),
+        (F4, &["F4"]),
+        (F5, &["F5"]),
+        (F6, &["F6"]),
+        (F7, &["F7"]),
+        (F8, &["F8"]),
+        (F9, &["F9"]),
+        (F10, &["F10"]),
+        (F11, &["F11"]),
+        (F12, &["F12"]),
+        (PrintScreen, &["Print Screen", "prtsc", "prt sc", "print"]),
+        (ScrollLock, &["Scroll Lock", "scrlk"]),
+        (Pause, &["Pause/Break", "pause", "break"]),
+        (Backtick, &["`", "backtick", "grave", "tilde", "~"]),
+        (Digit1, &["1"]),
+        (Digit2, &["2"]),
+        (Digit3, &["3"]),
+        (Digit4, &["4"]),
+        (Digit5, &["5"]),
+        (Digit6, &["6"]),
+        (Digit7, &["7"]),
+        (Digit8, &["8"]),
+        (Digit9, &["9"]),
+        (Digit0, &["0"]),
+        (Minus, &["-", "minus"]),
+        (Equals, &["=", "equals", "equal"]),
+        (Backspace, &["Backspace"]),
+        (Tab, &["Tab"]),
+        (Q, &["Q"]),
+        (W, &["W"]),
+        (E, &["E"]),
+        (R, &["R"]),
+        (T, &["T"]),
+        (Y, &["Y"]),
+        (U, &["U"]),
+        (I, &["I"]),
+        (O, &["O"]),
+        (P, &["P"]),
+        (LeftBracket, &["[", "left bracket"]),
+        (RightBracket, &["]", "right bracket"]),
+        (Backslash, &["\\ (ANSI)", "\\", "backslash"]),
+        (CapsLock, &["Caps Lock", "caps"]),
+        (A, &["A"]),
+        (S, &["S"]),
+        (D, &["D"]),
+        (F, &["F"]),
+        (G, &["G"]),
+        (H, &["H"]),
+        (J, &["J"]),
+        (K, &["K"]),
+        (L, &["L"]),
+        (Semicolon, &[";", "semicolon"]),
+        (Quote, &["'", "quote", "apostrophe"]),
+        (Pound, &["#", "pound", "hash"]),
+        (Enter, &["Enter", "return"]),
+        (LeftShift, &["Left Shift", "shift", "lshift"]),
+        (IsoBackslash, &["\\ (ISO)", "iso backslash"]),
+        (Z, &["Z"]),
+        (X, &["X"]),
+        (C, &["C"]),
+        (V, &["V"]),
+        (B, &["B"]),
+        (N, &["N"]),
+        (M, &["M"]),
+        (Comma, &[",", "comma"]),
+        (Period, &[".", "period", "dot"]),
+        (Slash, &["/", "slash"]),
+        (RightShift, &["Right Shift", "rshift"]),
+        (
+            LeftControl,
+            &["Left Control", "left ctrl", "lctrl", "control", "ctrl"],
+        ),
+        (
+            LeftWindows,
+            &[
+                "Left Windows",
+                "left win",
+                "left super",
+                "left meta",
+                "left gui",
+                "windows",
+                "win",
+                "super",
+                "meta",
+            ],
+        ),
+        (LeftAlt, &["Left Alt", "lalt", "alt"]),
+        (LeftFn, &["Left Fn"]),
+        (Space, &["Space", "spacebar"]),
+        (RightAlt, &["Right Alt", "ralt", "alt gr", "altgr"]),
+        (RightFn, &["Right Fn", "fn"]),
+        (Menu, &["Menu", "context menu", "apps"]),
+        (
+            RightWindows,
+            &[
+                "Right Windows",
+                "right win",
+                "right super",
+                "right meta",
+                "right gui",
+            ],
+        ),
+        (RightControl, &["Right Control", "right ctrl", "rctrl"]),
+        (Insert, &["Insert", "ins"]),
+        (Home, &["Home"]),
+        (PageUp, &["Page Up", "pgup"]),
+        (Delete, &["Delete", "del"]),
+        (End, &["End"]),
+        (PageDown, &["Page Down", "pgdn"]),
+        (UpArrow, &["Up Arrow", "up"]),
+        (LeftArrow, &["Left Arrow", "left"]),
+        (DownArrow, &["Down Arrow", "down"]),
+        (RightArrow, &["Right Arrow", "right"]),
+        (NumLock, &["Num Lock", "numlock"]),
+        (NumpadDivide, &["Number Pad /", "numpad /", "numpad divide"]),
+        (
+            NumpadMultiply,
+            &["Number Pad *", "numpad *", "numpad multiply"],
+        ),
+        (NumpadMinus, &["Number Pad -", "numpad -", "numpad minus"]),
+        (NumpadPlus, &["Number Pad +", "numpad +", "numpad plus"]),
+        (NumpadEnter, &["Number Pad Enter", "numpad enter"]),
+        (
+            NumpadDecimal,
+            &["Number Pad .", "numpad .", "numpad decimal"],
+        ),
+        (Numpad0, &["Number Pad 0", "numpad 0"]),
+        (Numpad1, &["Number Pad 1", "numpad 1"]),
+        (Numpad2, &["Number Pad 2", "numpad 2"]),
+        (Numpad3, &["Number Pad 3", "numpad 3"]),
+        (Numpad4, &["Number Pad 4", "numpad 4"]),
+        (Numpad5, &["Number Pad 5", "numpad 5"]),
+        (Numpad6, &["Number Pad 6", "numpad 6"]),
+        (Numpad7, &["Number Pad 7", "numpad 7"]),
+        (Numpad8, &["Number Pad 8", "numpad 8"]),
+        (Numpad9, &["Number Pad 9", "numpad 9"]),
+        (MediaPlayPause, &["Media Play/Pause", "play/pause", "play"]),
+        (MediaPrevious, &["Media Previous", "previous track", "prev"]),
+        (MediaNext, &["Media Next", "next track", "next"]),
+        (MediaStop, &["Media Stop", "stop"]),
+        (MediaMute, &["Media Mute", "mute"]),
+        (VolumeUp, &["Media Volume +", "volume up", "vol+"]),
+        (VolumeDown, &["Media Volume -", "volume down", "vol-"]),
+    ]
+};
+
+/// Lowercases `name`, strips the `"Key: "` prefix and collapses whitespace.
+pub(crate) fn normalise(name: &str) -> String {
+    let name = name.trim();
+    let name = match name.get(..4) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("key:") => &name[4..],
+        _ => name,
+    };
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+impl KeyCode {
+    /// Returns the key for an `OpenRGB` LED name such as `"Key: Escape"`,
+    /// or an alternative name such as `"esc"`.
+    ///
+    /// Names are case insensitive, the `"Key: "` prefix is optional.
+    /// Returns `None` if the name is not a known key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use openrgb2::KeyCode;
+    /// assert_eq!(KeyCode::from_name("Key: Escape"), Some(KeyCode::Escape));
+    /// assert_eq!(KeyCode::from_name("ESC"), Some(KeyCode::Escape));
+    /// assert_eq!(KeyCode::from_name("numpad 7"), Some(KeyCode::Numpad7));
+    /// assert_eq!(KeyCode::from_name("Logo"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = normalise(name);
+        KEY_NAMES
+            .iter()
+            .find(|(_, names)| names.iter().any(|n| normalise(n) == name))
+            .map(|(key, _)| *key)
+    }
+
+    /// Returns the name `OpenRGB` uses for this key, without the `"Key: "` prefix.
+    pub fn name(self) -> &'static str {
+        KEY_NAMES
+            .iter()
+            .find(|(key, _)| *key == self)
+            .map(|(_, names)| names[0])
+            .expect("every key has a name")
+    }
+}
+
+impl std::fmt::Display for KeyCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A group of keys that belong together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyGroup {
+    /// W, A, S and D.
+    Wasd,
+    /// The four arrow keys.
+    Arrows,
+    /// F1 to F12.
+    FunctionRow,
+    /// 1 to 0 above the letters.
+    NumberRow,
+    /// A to Z.
+    Letters,
+    /// All keys on the number pad, including num lock.
+    Numpad,
+    /// Shift, control, alt, windows and fn keys on both sides.
+    Modifiers,
+    /// Media control keys.
+    Media,
+}
+
+impl KeyGroup {
+    /// Returns the keys in this group.
+    pub fn keys(self) -> &'static [KeyCode] {
+        use KeyCode::*;
+        match self {
+            Self::Wasd => &[W, A, S, D],
+            Self::Arrows => &[UpArrow, LeftArrow, DownArrow, RightArrow],
+            Self::FunctionRow => &[F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12],
+            Self::NumberRow => &[
+                Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9, Digit0,
+            ],
+            Self::Letters => &[
+                A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+            ],
+            Self::Numpad => &[
+                NumLock,
+                NumpadDivide,
+                NumpadMultiply,
+                NumpadMinus,
+                NumpadPlus,
+                NumpadEnter,
+                NumpadDecimal,
+                Numpad0,
+                Numpad1,
+                Numpad2,
+                Numpad3,
+                Numpad4,
+                Numpad5,
+                Numpad6,
+                Numpad7,
+                Numpad8,
+                Numpad9,
+            ],
+            Self::Modifiers => &[
+                LeftShift,
+                RightShift,
+                LeftControl,
+                RightControl,
+                LeftAlt,
+                RightAlt,
+                LeftWindows,
+                RightWindows,
+                LeftFn,
+                RightFn,
+            ],
+            Self::Media => &[
+                MediaPlayPause,
+                MediaPrevious,
+                MediaNext,
+                MediaStop,
+                MediaMute,
+                VolumeUp,
+                VolumeDown,
+            ],
+        }
+    }
+
+    /// Returns true if `key` is part of this group.
+    pub fn contains(self, key: KeyCode) -> bool {
+        self.keys().contains(&key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalise() {
+        assert_eq!(normalise("Key: Number Pad  7"), "number pad 7");
+        assert_eq!(normalise("  KEY:Escape "), "escape");
+        assert_eq!(normalise("Logo"), "logo");
+        assert_eq!(normalise("Key"), "key");
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(
+            KeyCode::from_name("Key: \\ (ISO)"),
+            Some(KeyCode::IsoBackslash)
+        );
+        assert_eq!(
+            KeyCode::from_name("Key: \\ (ANSI)"),
+            Some(KeyCode::Backslash)
+        );
+        assert_eq!(
+            KeyCode::from_name("Key: Number Pad -"),
+            Some(KeyCode::NumpadMinus)
+        );
+        assert_eq!(KeyCode::from_name("-"), Some(KeyCode::Minus));
+        assert_eq!(KeyCode::from_name("Left Ctrl"), Some(KeyCode::LeftControl));
+        assert_eq!(
+            KeyCode::from_name("Key: Media Volume +"),
+            Some(KeyCode::VolumeUp)
+        );
+        assert_eq!(KeyCode::from_name("Logo"), None);
+    }
+
+    #[test]
+    fn test_names_round_trip() {
+        for (key, names) in KEY_NAMES {
+            assert_eq!(key.name(), names[0]);
+            for name in *names {
+                assert_eq!(KeyCode::from_name(name), Some(*key), "{name}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_groups() {
+        assert!(KeyGroup::Wasd.contains(KeyCode::A));
+        assert!(!KeyGroup::Wasd.contains(KeyCode::Q));
+        assert_eq!(KeyGroup::FunctionRow.keys().len(), 12);
+        assert_eq!(KeyGroup::Letters.keys().len(), 26);
+        assert!(KeyGroup::Numpad.contains(KeyCode::NumpadEnter));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Color, Command, Controller, OpenRgbResult};
+use crate::{Color, Command, Controller, KeyCode, OpenRgbResult};
 
 /// A single LED of a controller
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -42,6 +42,29 @@ impl<'c> Led<'c> {
         self.name
     }
 
+    /// Returns the alternative name of this LED, if the server provides one.
+    ///
+    /// Only supported in protocol version 5 and above.
+    pub fn alt_name(&self) -> Option<&str> {
+        self.controller
+            .led_alt_names()
+            .and_then(|names| names.get(self.id))
+            .map(String::as_str)
+            .filter(|name| !name.is_empty())
+    }
+
+    /// Returns the name and, if the server provides one, the alternative name of this LED.
+    pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name).chain(self.alt_name())
+    }
+
+    /// Returns the keyboard key of this LED, based on its name or alternative name.
+    ///
+    /// Returns `None` if this LED is not a (known) key.
+    pub fn key_code(&self) -> Option<KeyCode> {
+        self.names().find_map(KeyCode::from_name)
+    }
+
     /// Returns color of this LED after the last [`crate::Controller::sync_controller_data()`] call.
     pub fn color(&self) -> Color {
         self.color
@@ -0,0 +1,204 @@
+use array2d::Array2D;
+
+use crate::{Color, Led, OpenRgbError, OpenRgbResult, Zone};
+
+/// Value used in a matrix map for positions without an LED.
+pub(crate) const MATRIX_HOLE: u32 = u32::MAX;
+
+/// Grid view of a [`crate::ZoneType::Matrix`] zone, such as a keyboard or LED panel.
+///
+/// Positions are addressed as `(row, col)`, with `(0, 0)` in the top left.
+/// Not every position has to contain an LED, for example the space next to a wide key.
+///
+/// Use [`Zone::matrix()`] to get the matrix of a zone.
+#[derive(Debug, Clone)]
+pub struct ZoneMatrix<'c> {
+    zone: Zone<'c>,
+    map: &'c Array2D<u32>,
+}
+
+impl<'c> ZoneMatrix<'c> {
+    pub(crate) fn new(zone: Zone<'c>, map: &'c Array2D<u32>) -> Self {
+        Self { zone, map }
+    }
+
+    /// Returns the zone of this matrix.
+    pub fn zone(&self) -> &Zone<'c> {
+        &self.zone
+    }
+
+    /// Returns the number of rows in this matrix.
+    pub fn height(&self) -> usize {
+        self.map.num_rows()
+    }
+
+    /// Returns the number of columns in this matrix.
+    pub fn width(&self) -> usize {
+        self.map.num_columns()
+    }
+
+    /// Returns the index in the zone of the LED at `(row, col)`.
+    ///
+    /// Returns `None` if the position is out of bounds or has no LED.
+    pub fn led_index(&self, row: usize, col: usize) -> Option<usize> {
+        led_index(self.map, self.zone.num_leds(), row, col)
+    }
+
+    /// Returns the LED at `(row, col)`.
+    ///
+    /// Returns `None` if the position is out of bounds or has no LED.
+    pub fn get(&self, row: usize, col: usize) -> Option<Led<'c>> {
+        let idx = self.led_index(row, col)?;
+        Some(Led::new(self.zone.offset() + idx, self.zone.controller()))
+    }
+
+    /// Returns the `(row, col)` position of the LED with index `led_idx` in the zone.
+    pub fn position(&self, led_idx: usize) -> Option<(usize, usize)> {
+        self.map
+            .enumerate_row_major()
+            .find(|&(_, &idx)| idx as usize == led_idx && idx != MATRIX_HOLE)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Returns an iterator over the LEDs in `row`, from left to right.
+    ///
+    /// Positions without an LED are `None`.
+    pub fn row(&self, row: usize) -> impl Iterator<Item = Option<Led<'c>>> {
+        let width = if row < self.height() { self.width() } else { 0 };
+        (0..width).map(move |col| self.get(row, col))
+    }
+
+    /// Returns an iterator over the LEDs in `col`, from top to bottom.
+    ///
+    /// Positions without an LED are `None`.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = Option<Led<'c>>> {
+        let height = if col < self.width() { self.height() } else { 0 };
+        (0..height).map(move |row| self.get(row, col))
+    }
+
+    /// Returns an iterator over all rows, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = Option<Led<'c>>>> {
+        (0..self.height()).map(|row| self.row(row))
+    }
+
+    /// Returns an iterator over all columns, from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = Option<Led<'c>>>> {
+        (0..self.width()).map(|col| self.column(col))
+    }
+
+    /// Maps a `width` by `height` image in row major order onto the LEDs of this matrix.
+    ///
+    /// The image is scaled to the size of the matrix using nearest neighbour sampling.
+    /// Returns a color per LED in the zone, LEDs that are not in the matrix map are black.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pixels` does not contain exactly `width * height` colors.
+    pub fn map_image(
+        &self,
+        width: usize,
+        height: usize,
+        pixels: &[Color],
+    ) -> OpenRgbResult<Vec<Color>> {
+        let mut colors = vec![Color::default(); self.zone.num_leds()];
+        for (idx, color) in sample_image(self.map, self.zone.num_leds(), width, height, pixels)? {
+            colors[idx] = color;
+        }
+        Ok(colors)
+    }
+
+    /// Returns the color of `pixels` for every LED in the matrix map, as `(index in zone, color)`.
+    pub(crate) fn sample_image(
+        &self,
+        width: usize,
+        height: usize,
+        pixels: &[Color],
+    ) -> OpenRgbResult<Vec<(usize, Color)>> {
+        sample_image(self.map, self.zone.num_leds(), width, height, pixels)
+    }
+}
+
+/// Index in the zone of the LED at `(row, col)` in `map`, skipping holes and invalid indices.
+fn led_index(map: &Array2D<u32>, num_leds: usize, row: usize, col: usize) -> Option<usize> {
+    map.get(row, col)
+        .copied()
+        .filter(|&idx| idx != MATRIX_HOLE && (idx as usize) < num_leds)
+        .map(|idx| idx as usize)
+}
+
+/// Samples a `width` by `height` image with nearest neighbour sampling at every LED in `map`.
+fn sample_image(
+    map: &Array2D<u32>,
+    num_leds: usize,
+    width: usize,
+    height: usize,
+    pixels: &[Color],
+) -> OpenRgbResult<Vec<(usize, Color)>> {
+    if pixels.is_empty() || width.checked_mul(height) != Some(pixels.len()) {
+        return Err(OpenRgbError::CommandError(format!(
+            "Image of {width}x{height} was given {} pixels",
+            pixels.len()
+        )));
+    }
+    let (rows, cols) = (map.num_rows(), map.num_columns());
+    let mut colors = Vec::with_capacity(num_leds);
+    for row in 0..rows {
+        let y = row * height / rows;
+        for col in 0..cols {
+            let Some(idx) = led_index(map, num_leds, row, col) else {
+                continue;
+            };
+            let x = col * width / cols;
+            colors.push((idx, pixels[y * width + x]));
+        }
+    }
+    Ok(colors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyboard() -> Array2D<u32> {
+        // 2x3 matrix, the bottom middle key is wide and leaves a hole
+        Array2D::from_rows(&[vec![0, 1, 2], vec![3, MATRIX_HOLE, 4]]).unwrap()
+    }
+
+    #[test]
+    fn test_led_index() {
+        let map = keyboard();
+        assert_eq!(led_index(&map, 5, 0, 2), Some(2));
+        assert_eq!(led_index(&map, 5, 1, 2), Some(4));
+        assert_eq!(led_index(&map, 5, 1, 1), None);
+        assert_eq!(led_index(&map, 5, 2, 0), None);
+        // index beyond the zone size
+        assert_eq!(led_index(&map, 4, 1, 2), None);
+    }
+
+    #[test]
+    fn test_map_image() -> OpenRgbResult<()> {
+        let map = keyboard();
+        let (r, g, b) = (
+            Color::new(255, 0, 0),
+            Color::new(0, 255, 0),
+            Color::new(0, 0, 255),
+        );
+        let colors = sample_image(&map, 5, 3, 2, &[r, g, b, b, g, r])?;
+        // the hole is skipped
+        assert_eq!(colors, vec![(0, r), (1, g), (2, b), (3, b), (4, r)]);
+
+        // 6x4 image is scaled down to 3x2
+        let mut image = vec![Color::default(); 24];
+        image[2 * 6 + 4] = r; // row 2, col 4 maps to row 1, col 2
+        let colors = sample_image(&map, 5, 6, 4, &image)?;
+        assert_eq!(colors.len(), 5);
+        assert_eq!(
+            colors.iter().filter(|(_, c)| *c == r).collect::<Vec<_>>(),
+            [&(4, r)]
+        );
+
+        assert!(sample_image(&map, 5, 3, 3, &[r; 6]).is_err());
+        assert!(sample_image(&map, 5, 0, 0, &[]).is_err());
+        Ok(())
+    }
+}
@@ -1,21 +1,65 @@
 //! Wrapper around the `OpenRGB` client to make it friendlier to use.
 
+mod brightness;
+mod calibration;
+mod capability;
 mod command;
 mod controller;
+mod device_id;
 mod group;
+mod keymap;
 mod led;
+mod matrix;
 mod mode;
+mod mode_spec;
 mod plugin;
+mod profile;
+mod rescan;
+mod save_guard;
+mod scheduler;
 mod segment;
+mod segment_manager;
+mod selector;
+mod snapshot;
+#[cfg(feature = "toml")]
+mod toml_file;
+mod util;
+mod virtual_strip;
 mod zone;
 
-pub use {command::*, controller::*, group::*, led::*, mode::*, segment::*, zone::*};
+pub use {
+    brightness::PowerLimit,
+    calibration::*,
+    capability::*,
+    command::*,
+    controller::*,
+    device_id::*,
+    group::*,
+    keymap::{KeyCode, KeyGroup},
+    led::*,
+    matrix::*,
+    mode::*,
+    mode_spec::{ModeIssue, ModeReport, ModeResult, ModeSpec},
+    plugin::*,
+    profile::*,
+    rescan::{DeviceChange, DeviceListDiff},
+    save_guard::*,
+    scheduler::*,
+    segment::*,
+    segment_manager::{SegmentIssue, SegmentManager},
+    selector::*,
+    snapshot::*,
+    virtual_strip::*,
+    zone::*,
+};
+
+use std::time::Duration;
 
 use tokio::net::ToSocketAddrs;
 
 use crate::{
     DEFAULT_PROTOCOL, OpenRgbError,
-    client::plugin::OpenRgbPlugin,
+    client::brightness::Brightness,
     data::DeviceType,
     error::OpenRgbResult,
     protocol::{DEFAULT_ADDR, OpenRgbProtocol},
@@ -30,6 +74,7 @@ use crate::{
 /// # Example
 pub struct OpenRgbClient {
     proto: OpenRgbProtocol,
+    brightness: Brightness,
 }
 
 impl OpenRgbClient {
@@ -76,7 +121,10 @@ impl OpenRgbClient {
         protocol_version: u32,
     ) -> OpenRgbResult<Self> {
         let client = OpenRgbProtocol::connect_to(addr, protocol_version).await?;
-        Ok(Self { proto: client })
+        Ok(Self {
+            proto: client,
+            brightness: Brightness::default(),
+        })
     }
 }
 
@@ -120,7 +168,26 @@ impl OpenRgbClient {
     /// This function returns an error if communication with the `OpenRGB` SDK server fails.
     pub async fn get_controller(&self, i: usize) -> OpenRgbResult<Controller> {
         let c_data = self.proto.get_controller(i as u32).await?;
-        Ok(Controller::new(i, self.proto.clone(), c_data))
+        Ok(Controller::new(
+            i,
+            self.proto.clone(),
+            c_data,
+            self.brightness.clone(),
+        ))
+    }
+
+    /// Returns the master brightness of this client, between 0.0 and 1.0.
+    pub fn brightness(&self) -> f32 {
+        self.brightness.get()
+    }
+
+    /// Sets the master brightness, clamped to `0.0..=1.0`.
+    ///
+    /// Every color written to a controller created by this client is scaled by this brightness,
+    /// on top of the brightness of the controller itself.
+    /// This also applies to controllers that were created before calling this.
+    pub fn set_brightness(&self, brightness: f32) {
+        self.brightness.set(brightness);
     }
 }
 
@@ -168,10 +235,85 @@ impl OpenRgbClient {
         self.proto.rescan_devices().await
     }
 
+    /// Forces the `OpenRGB` instance to rescan for devices, and waits for the scan to finish.
+    ///
+    /// The scan is considered finished once the server notified a device list update,
+    /// or the number of controllers changed, and nothing changed afterwards for a short while.
+    /// If that does not happen within `timeout`, for example because the server does not
+    /// send notifications and the devices stayed the same, the controllers are fetched anyway.
+    ///
+    /// Returns the controllers after the rescan, and how they differ from before the rescan.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server does not support rescanning (protocol version < 5),
+    /// or communication with the server fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use openrgb2::*;
+    /// # use std::time::Duration;
+    /// # async fn example() -> OpenRgbResult<()> {
+    /// let client = OpenRgbClient::connect().await?;
+    /// let (controllers, diff) = client.rescan_and_wait(Duration::from_secs(10)).await?;
+    /// for device in &diff.added {
+    ///     println!("new device: {device}");
+    /// }
+    /// for device in &diff.removed {
+    ///     println!("removed device: {device}");
+    /// }
+    /// println!("{} controllers", controllers.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rescan_and_wait(
+        &self,
+        timeout: Duration,
+    ) -> OpenRgbResult<(ControllerGroup, DeviceListDiff)> {
+        let old = self.get_all_controllers().await?;
+        // forget notifications from before the rescan
+        self.proto.take_device_list_updated().await;
+        self.proto.rescan_devices().await?;
+        let settled = rescan::wait_for_device_list(old.len(), timeout, || async {
+            let count = self.proto.get_controller_count().await? as usize;
+            Ok((count, self.proto.take_device_list_updated().await))
+        })
+        .await?;
+        if !settled {
+            tracing::warn!("Device list did not settle within {timeout:?} after rescan");
+        }
+        let new = self.get_all_controllers().await?;
+        let diff = DeviceListDiff::between(&old, &new);
+        Ok((new, diff))
+    }
+
     /// Returns a list of available plugins installed on `OpenRGB`.
     pub async fn get_plugins(&self) -> OpenRgbResult<Vec<OpenRgbPlugin>> {
         let plugins_raw = self.proto.get_plugins().await?;
-        let plugins = plugins_raw.into_iter().map(OpenRgbPlugin::from).collect();
+        let plugins = plugins_raw
+            .into_iter()
+            .map(|data| OpenRgbPlugin::new(&self.proto, data))
+            .collect();
         Ok(plugins)
     }
+
+    /// Returns the Effects plugin, see [`EffectsPlugin`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin is not installed,
+    /// or communication with the `OpenRGB` SDK server fails.
+    pub async fn get_effects_plugin(&self) -> OpenRgbResult<EffectsPlugin> {
+        self.get_plugins()
+            .await?
+            .into_iter()
+            .find_map(|plugin| match plugin {
+                OpenRgbPlugin::Effects(effects) => Some(effects),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                OpenRgbError::CommandError("OpenRGB Effects Plugin is not installed".to_owned())
+            })
+    }
 }
@@ -1,6 +1,9 @@
 use std::marker::PhantomData;
 
-use crate::{Controller, Direction, ModeData, ModeFlag, OpenRgbError, OpenRgbResult};
+use crate::{
+    Color, ColorMode, Controller, Direction, ModeCapabilities, ModeData, ModeFlag, OpenRgbError,
+    OpenRgbResult,
+};
 
 pub use flagset::FlagSet;
 
@@ -56,6 +59,11 @@ impl<'c> ControllerMode<'c> {
         self.is_active
     }
 
+    /// Returns a summary of what this mode supports.
+    pub fn capabilities(&self) -> ModeCapabilities {
+        ModeCapabilities::new(self)
+    }
+
     /// Creates a [`ControllerModeBuilder`] for this mode.
     ///
     /// This lets you configure the mode and change it on the controller.
@@ -69,6 +77,8 @@ impl<'c> ControllerMode<'c> {
             pub fn name(&self) -> &str;
             /// The flags of this mode.
             pub(crate) fn flags(&self) -> FlagSet<ModeFlag>;
+            /// The index of this mode in the controller.
+            pub(crate) fn id(&self) -> usize;
             /// The speed of this mode, if available.
             pub fn speed(&self) -> Option<u32>;
             /// The minimum speed of this mode, if available.
@@ -83,8 +93,67 @@ impl<'c> ControllerMode<'c> {
             pub fn brightness_max(&self) -> Option<u32>;
             /// The direction of this mode, if available.
             pub fn direction(&self) -> Option<Direction>;
+            /// The color mode of this mode.
+            pub fn color_mode(&self) -> ColorMode;
+            /// The mode specific colors of this mode.
+            pub fn colors(&self) -> &[Color];
+            /// The minimum number of mode specific colors, if available.
+            pub fn colors_min(&self) -> Option<u32>;
+            /// The maximum number of mode specific colors, if available.
+            pub fn colors_max(&self) -> Option<u32>;
+            /// Whether this mode supports the given color mode.
+            pub fn supports_color_mode(&self, color_mode: ColorMode) -> bool;
+            /// The directions this mode supports, empty if it has no direction.
+            pub fn supported_directions(&self) -> Vec<Direction>;
+            /// Whether this mode supports the given direction.
+            pub fn supports_direction(&self, dir: Direction) -> bool;
         }
     }
+
+    /// The speed of this mode as a percentage of its range, if available.
+    ///
+    /// 0.0 is [`Self::speed_min()`] and 100.0 is [`Self::speed_max()`], also when the minimum is the larger value.
+    pub fn speed_pct(&self) -> Option<f32> {
+        Some(range_to_pct(
+            self.speed_min()?,
+            self.speed_max()?,
+            self.speed()?,
+        ))
+    }
+
+    /// The brightness of this mode as a percentage of its range, if available.
+    ///
+    /// See [`Self::speed_pct()`].
+    pub fn brightness_pct(&self) -> Option<f32> {
+        Some(range_to_pct(
+            self.brightness_min()?,
+            self.brightness_max()?,
+            self.brightness()?,
+        ))
+    }
+}
+
+/// Returns true if `value` is between `a` and `b`, in either order.
+pub(crate) fn in_range(a: u32, b: u32, value: u32) -> bool {
+    (a.min(b)..=a.max(b)).contains(&value)
+}
+
+/// Maps `pct` (clamped to `0.0..=100.0`) to a value between `min` and `max`.
+///
+/// Some devices report `min > max`, in which case the value decreases as `pct` increases.
+fn pct_to_range(min: u32, max: u32, pct: f32) -> u32 {
+    let t = f64::from(pct.clamp(0.0, 100.0)) / 100.0;
+    let value = f64::from(min) + (f64::from(max) - f64::from(min)) * t;
+    value.round() as u32
+}
+
+/// Inverse of [`pct_to_range()`].
+fn range_to_pct(min: u32, max: u32, value: u32) -> f32 {
+    if min == max {
+        return 100.0;
+    }
+    let pct = (f64::from(value) - f64::from(min)) / (f64::from(max) - f64::from(min)) * 100.0;
+    pct.clamp(0.0, 100.0) as f32
 }
 
 /// Builder for a controller mode.
@@ -130,7 +199,7 @@ impl ControllerModeBuilder<'_> {
             ));
         };
 
-        if speed < speed_min || speed > speed_max {
+        if !in_range(speed_min, speed_max, speed) {
             return Err(OpenRgbError::CommandError(format!(
                 "Speed must be between {speed_min} and {speed_max}, got: {speed}",
             )));
@@ -188,7 +257,7 @@ impl ControllerModeBuilder<'_> {
             ));
         };
 
-        if brightness < brightness_min || brightness > brightness_max {
+        if !in_range(brightness_min, brightness_max, brightness) {
             return Err(OpenRgbError::CommandError(format!(
                 "Brightness must be between {brightness_min} and {brightness_max}, got: {brightness}",
             )));
@@ -226,19 +295,165 @@ impl ControllerModeBuilder<'_> {
         }
     }
 
+    /// Sets the speed of this mode to `pct` percent of its range, `pct` is clamped to `0.0..=100.0`.
+    ///
+    /// 0% is the minimum speed and 100% the maximum speed reported by the device,
+    /// this also works for devices that report a minimum larger than the maximum.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this mode does not support speed.
+    pub fn set_speed_pct(&mut self, pct: f32) -> OpenRgbResult<&mut Self> {
+        let (Some(speed_min), Some(speed_max)) = (self.data.speed_min(), self.data.speed_max())
+        else {
+            return Err(OpenRgbError::CommandError(
+                "Mode does not support speed".to_owned(),
+            ));
+        };
+        self.set_speed(pct_to_range(speed_min, speed_max, pct))
+    }
+
+    /// Sets the brightness of this mode to `pct` percent of its range, `pct` is clamped to `0.0..=100.0`.
+    ///
+    /// See [`Self::set_speed_pct()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this mode does not support brightness.
+    pub fn set_brightness_pct(&mut self, pct: f32) -> OpenRgbResult<&mut Self> {
+        let (Some(brightness_min), Some(brightness_max)) =
+            (self.data.brightness_min(), self.data.brightness_max())
+        else {
+            return Err(OpenRgbError::CommandError(
+                "Mode does not support brightness".to_owned(),
+            ));
+        };
+        self.set_brightness(pct_to_range(brightness_min, brightness_max, pct))
+    }
+
     /// Sets the direction of this mode.
     ///
     /// # Errors
     ///
-    /// Returns an error if this mode does not support a direction.
+    /// Returns an error if this mode does not support `dir`,
+    /// see [`ControllerMode::supported_directions()`].
     pub fn set_direction(&mut self, dir: Direction) -> OpenRgbResult<&mut Self> {
-        if !self.data.flags().contains(ModeFlag::HasDirection) {
+        if !self.data.supports_direction(dir) {
+            let supported = self.data.supported_directions();
+            return Err(OpenRgbError::CommandError(match supported.is_empty() {
+                true => "Mode does not support direction".to_owned(),
+                false => {
+                    format!("Mode does not support direction {dir:?}, supported: {supported:?}")
+                }
+            }));
+        }
+
+        self.data.set_direction(dir);
+        Ok(self)
+    }
+
+    /// Sets the mode specific colors of this mode and switches to [`ColorMode::ModeSpecific`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use openrgb2::{Color, OpenRgbClient, OpenRgbResult};
+    /// # async fn example() -> OpenRgbResult<()> {
+    /// # let client = OpenRgbClient::connect().await?;
+    /// let controller = client.get_controller(0).await?;
+    /// let breathing = controller
+    ///     .mode_iter()
+    ///     .find(|m| m.name() == "Breathing")
+    ///     .expect("controller has a breathing mode");
+    /// breathing
+    ///     .builder()
+    ///     .set_colors([Color::new(128, 0, 128)])?
+    ///     .execute(&controller)
+    ///     .await
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this mode does not support mode specific colors,
+    /// or if the number of colors is outside of [`ControllerMode::colors_min()`] and [`ControllerMode::colors_max()`].
+    pub fn set_colors<C: Into<Color>>(
+        &mut self,
+        colors: impl IntoIterator<Item = C>,
+    ) -> OpenRgbResult<&mut Self> {
+        if !self.data.flags().contains(ModeFlag::HasModeSpecificColor) {
+            return Err(OpenRgbError::CommandError(
+                "Mode does not support mode specific colors".to_owned(),
+            ));
+        }
+        let (Some(colors_min), Some(colors_max)) = (self.data.colors_min(), self.data.colors_max())
+        else {
             return Err(OpenRgbError::CommandError(
-                "Mode does not support direction".to_owned(),
+                "Mode does not support mode specific colors".to_owned(),
             ));
+        };
+
+        let colors = colors.into_iter().map(Into::into).collect::<Vec<_>>();
+        let count = colors.len();
+        if count < colors_min as usize || count > colors_max as usize {
+            return Err(OpenRgbError::CommandError(format!(
+                "Number of colors must be between {colors_min} and {colors_max}, got: {count}",
+            )));
         }
 
-        self.data.set_direction(dir);
+        self.data.set_colors(colors);
+        self.data.set_color_mode(ColorMode::ModeSpecific);
         Ok(self)
     }
+
+    /// Sets the color mode of this mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this mode does not support `color_mode`.
+    pub fn set_color_mode(&mut self, color_mode: ColorMode) -> OpenRgbResult<&mut Self> {
+        if !self.data.supports_color_mode(color_mode) {
+            return Err(OpenRgbError::CommandError(format!(
+                "Mode does not support color mode {color_mode:?}"
+            )));
+        }
+
+        self.data.set_color_mode(color_mode);
+        Ok(self)
+    }
+
+    /// Lets the device pick random colors, equivalent to `set_color_mode(ColorMode::Random)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this mode does not support random colors.
+    pub fn set_random(&mut self) -> OpenRgbResult<&mut Self> {
+        self.set_color_mode(ColorMode::Random)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pct_to_range() {
+        assert_eq!(pct_to_range(0, 100, 50.0), 50);
+        assert_eq!(pct_to_range(10, 20, 0.0), 10);
+        assert_eq!(pct_to_range(10, 20, 150.0), 20);
+        // reversed range, e.g. a delay where a lower value is faster
+        assert_eq!(pct_to_range(300, 100, 0.0), 300);
+        assert_eq!(pct_to_range(300, 100, 100.0), 100);
+        assert_eq!(pct_to_range(300, 100, 25.0), 250);
+    }
+
+    #[test]
+    fn test_range_to_pct() {
+        assert!((range_to_pct(0, 200, 50) - 25.0).abs() < f32::EPSILON);
+        assert!((range_to_pct(300, 100, 250) - 25.0).abs() < f32::EPSILON);
+        assert!((range_to_pct(5, 5, 5) - 100.0).abs() < f32::EPSILON);
+        assert!(in_range(300, 100, 200));
+        assert!(in_range(100, 300, 300));
+        assert!(!in_range(300, 100, 50));
+    }
 }
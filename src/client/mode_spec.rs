@@ -0,0 +1,310 @@
+use crate::{
+    Color, ColorMode, Controller, ControllerMode, ControllerModeBuilder, Direction, OpenRgbError,
+    OpenRgbResult,
+};
+
+/// Device independent description of a hardware mode, used by [`crate::ControllerGroup::apply_mode()`].
+///
+/// Speed and brightness are percentages of the range each device reports,
+/// so the same spec gives comparable results on devices with different ranges.
+///
+/// # Example
+///
+/// ```no_run
+/// # use openrgb2::*;
+/// # async fn example(group: &ControllerGroup) {
+/// let spec = ModeSpec::new("Breathing")
+///     .with_alternative("Pulse")
+///     .with_speed(25.0)
+///     .with_colors([Color::new(255, 0, 0), Color::new(0, 0, 255)]);
+/// let report = group.apply_mode(&spec).await;
+/// for result in report.non_compliant() {
+///     println!("controller {}: {:?}", result.controller_id, result.issues);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModeSpec {
+    names: Vec<String>,
+    speed: Option<f32>,
+    brightness: Option<f32>,
+    direction: Option<Direction>,
+    colors: Option<Vec<Color>>,
+}
+
+impl ModeSpec {
+    /// Creates a spec for the mode named `name`.
+    ///
+    /// Names are matched ignoring case, spaces and punctuation,
+    /// so `"Color Cycle"` also matches a mode named `"color_cycle"`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            names: vec![name.into()],
+            speed: None,
+            brightness: None,
+            direction: None,
+            colors: None,
+        }
+    }
+
+    /// Adds an alternative name, used for devices that name the same effect differently.
+    ///
+    /// Names are tried in the order they are added.
+    #[must_use]
+    pub fn with_alternative(mut self, name: impl Into<String>) -> Self {
+        self.names.push(name.into());
+        self
+    }
+
+    /// Sets the speed as a percentage (`0.0..=100.0`) of each device's speed range.
+    #[must_use]
+    pub fn with_speed(mut self, pct: f32) -> Self {
+        self.speed = Some(pct);
+        self
+    }
+
+    /// Sets the brightness as a percentage (`0.0..=100.0`) of each device's brightness range.
+    #[must_use]
+    pub fn with_brightness(mut self, pct: f32) -> Self {
+        self.brightness = Some(pct);
+        self
+    }
+
+    /// Sets the direction.
+    #[must_use]
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Sets the mode specific colors.
+    ///
+    /// If a device needs fewer colors the list is truncated,
+    /// if it needs more the colors are repeated.
+    #[must_use]
+    pub fn with_colors<C: Into<Color>>(mut self, colors: impl IntoIterator<Item = C>) -> Self {
+        self.colors = Some(colors.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Returns the mode of `controller` that matches this spec best.
+    ///
+    /// An exact name match is preferred over a match that ignores punctuation,
+    /// which is preferred over a mode whose name contains the spec name.
+    /// If colors are requested, modes that support mode specific colors are preferred.
+    pub fn find_mode<'c>(&self, controller: &'c Controller) -> Option<ControllerMode<'c>> {
+        controller
+            .mode_iter()
+            .filter_map(|mode| {
+                let (score, name_idx) = self
+                    .names
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(idx, name)| Some((match_score(name, mode.name())?, idx)))
+                    .min()?;
+                let no_colors =
+                    self.colors.is_some() && !mode.supports_color_mode(ColorMode::ModeSpecific);
+                Some(((score, name_idx, no_colors), mode))
+            })
+            .min_by_key(|(key, _)| *key)
+            .map(|(_, mode)| mode)
+    }
+
+    /// Configures `mode` according to this spec, returning the parts that could not be applied.
+    fn configure<'c>(
+        &self,
+        mode: &ControllerMode<'c>,
+    ) -> (ControllerModeBuilder<'c>, Vec<ModeIssue>) {
+        let mut builder = mode.builder();
+        let mut issues = Vec::new();
+        if let Some(pct) = self.speed {
+            if builder.set_speed_pct(pct).is_err() {
+                issues.push(ModeIssue::SpeedUnsupported);
+            }
+        }
+        if let Some(pct) = self.brightness {
+            if builder.set_brightness_pct(pct).is_err() {
+                issues.push(ModeIssue::BrightnessUnsupported);
+            }
+        }
+        if let Some(direction) = self.direction {
+            if builder.set_direction(direction).is_err() {
+                issues.push(ModeIssue::DirectionUnsupported(direction));
+            }
+        }
+        if let Some(colors) = &self.colors {
+            match (mode.colors_min(), mode.colors_max()) {
+                (Some(min), Some(max)) if mode.supports_color_mode(ColorMode::ModeSpecific) => {
+                    let fitted = fit_colors(colors, min as usize, max as usize);
+                    if fitted.len() != colors.len() {
+                        issues.push(ModeIssue::ColorsAdjusted {
+                            requested: colors.len(),
+                            applied: fitted.len(),
+                        });
+                    }
+                    if builder.set_colors(fitted).is_err() {
+                        issues.push(ModeIssue::ColorsUnsupported);
+                    }
+                }
+                _ => issues.push(ModeIssue::ColorsUnsupported),
+            }
+        }
+        (builder, issues)
+    }
+
+    /// Applies this spec to `controller`.
+    pub(crate) async fn apply(&self, controller: &Controller) -> ModeResult {
+        let mut result = ModeResult {
+            controller_id: controller.id(),
+            mode: None,
+            issues: Vec::new(),
+            error: None,
+        };
+        let Some(mode) = self.find_mode(controller) else {
+            result.issues.push(ModeIssue::NoMatchingMode);
+            return result;
+        };
+        result.mode = Some(mode.name().to_owned());
+        let (builder, issues) = self.configure(&mode);
+        result.issues = issues;
+        result.error = builder.execute(controller).await.err();
+        result
+    }
+}
+
+/// Part of a [`ModeSpec`] that a controller could not comply with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeIssue {
+    /// The controller has no mode matching the spec, nothing was changed.
+    NoMatchingMode,
+    /// The mode has no speed setting.
+    SpeedUnsupported,
+    /// The mode has no brightness setting.
+    BrightnessUnsupported,
+    /// The mode does not support this direction.
+    DirectionUnsupported(Direction),
+    /// The mode has no mode specific colors.
+    ColorsUnsupported,
+    /// The number of colors was changed to fit the mode.
+    ColorsAdjusted {
+        /// Number of colors in the spec.
+        requested: usize,
+        /// Number of colors that were applied.
+        applied: usize,
+    },
+}
+
+/// Result of applying a [`ModeSpec`] to a single controller.
+#[derive(Debug)]
+pub struct ModeResult {
+    /// Id of the controller.
+    pub controller_id: usize,
+    /// Name of the mode that was applied, `None` if no mode matched.
+    pub mode: Option<String>,
+    /// Parts of the spec that could not be applied.
+    pub issues: Vec<ModeIssue>,
+    /// Error returned while updating the mode.
+    pub error: Option<OpenRgbError>,
+}
+
+impl ModeResult {
+    /// Returns true if the mode was applied without issues.
+    pub fn is_compliant(&self) -> bool {
+        self.mode.is_some() && self.issues.is_empty() && self.error.is_none()
+    }
+}
+
+/// Result of [`crate::ControllerGroup::apply_mode()`].
+#[derive(Debug, Default)]
+pub struct ModeReport {
+    /// Result per controller, in the order of the group.
+    pub results: Vec<ModeResult>,
+}
+
+impl ModeReport {
+    /// Returns true if every controller applied the spec without issues.
+    pub fn is_compliant(&self) -> bool {
+        self.results.iter().all(ModeResult::is_compliant)
+    }
+
+    /// Returns the results of controllers that could not fully comply.
+    pub fn non_compliant(&self) -> impl Iterator<Item = &ModeResult> {
+        self.results.iter().filter(|r| !r.is_compliant())
+    }
+
+    /// Returns the first error that occurred while updating a controller.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first controller that failed.
+    pub fn into_result(self) -> OpenRgbResult<Self> {
+        match self.results.iter().position(|r| r.error.is_some()) {
+            Some(idx) => {
+                let mut results = self.results;
+                Err(results.swap_remove(idx).error.expect("checked above"))
+            }
+            None => Ok(self),
+        }
+    }
+}
+
+/// Compares a spec name with a mode name, lower is better.
+fn match_score(spec: &str, mode: &str) -> Option<u8> {
+    if spec.eq_ignore_ascii_case(mode) {
+        return Some(0);
+    }
+    let (spec, mode) = (normalise_name(spec), normalise_name(mode));
+    if spec.is_empty() || mode.is_empty() {
+        None
+    } else if spec == mode {
+        Some(1)
+    } else if mode.contains(&spec) {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// Lowercases `name` and strips everything that is not alphanumeric.
+fn normalise_name(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Repeats or truncates `colors` to a length in `min..=max`.
+fn fit_colors(colors: &[Color], min: usize, max: usize) -> Vec<Color> {
+    let (min, max) = (min.min(max), min.max(max));
+    let len = colors.len().clamp(min, max);
+    if colors.is_empty() {
+        return vec![Color::default(); len];
+    }
+    colors.iter().copied().cycle().take(len).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_score() {
+        assert_eq!(match_score("Breathing", "breathing"), Some(0));
+        assert_eq!(match_score("Color Cycle", "color_cycle"), Some(1));
+        assert_eq!(match_score("Breathing", "Breathing Rainbow"), Some(2));
+        assert_eq!(match_score("Breathing", "Static"), None);
+        assert_eq!(match_score("--", "Static"), None);
+        // a short mode name is not a match for a longer spec
+        assert_eq!(match_score("Rainbow Wave", "Wave"), None);
+        assert_eq!(match_score("Breathing Static", "Static"), None);
+    }
+
+    #[test]
+    fn test_fit_colors() {
+        let (r, g) = (Color::new(255, 0, 0), Color::new(0, 255, 0));
+        assert_eq!(fit_colors(&[r, g], 1, 4), [r, g]);
+        assert_eq!(fit_colors(&[r, g], 1, 1), [r]);
+        assert_eq!(fit_colors(&[r, g], 3, 4), [r, g, r]);
+        assert_eq!(fit_colors(&[], 1, 1), [Color::default()]);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{OpenRgbError, OpenRgbResult, PluginData, PluginEffect, protocol::OpenRgbProtocol};
+
+/// Handle to the [`OpenRGB` Effects plugin](https://openrgb.org/plugin_effects.html),
+/// obtained from [`crate::OpenRgbClient::get_plugins()`].
+///
+/// The plugin runs effects inside `OpenRGB`, so they keep running without this client.
+/// Its SDK only supports listing, starting and stopping effects;
+/// effects have to be configured in the `OpenRGB` user interface.
+///
+/// # Example
+///
+/// ```no_run
+/// # use openrgb2::*;
+/// # async fn example() -> OpenRgbResult<()> {
+/// let client = OpenRgbClient::connect().await?;
+/// let effects = client.get_effects_plugin().await?;
+/// for effect in effects.get_effects().await? {
+///     println!("{}: {}", effect.name(), effect.description());
+/// }
+/// effects.stop_all_effects().await?;
+/// effects.start_effect("Rainbow Wave").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct EffectsPlugin {
+    proto: OpenRgbProtocol,
+    data: PluginData,
+}
+
+impl std::fmt::Debug for EffectsPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EffectsPlugin")
+            .field("data", &self.data)
+            .finish()
+    }
+}
+
+impl EffectsPlugin {
+    pub(crate) fn new(proto: OpenRgbProtocol, data: PluginData) -> Self {
+        Self { proto, data }
+    }
+
+    /// Returns the name, version and id of the plugin.
+    pub fn data(&self) -> &PluginData {
+        &self.data
+    }
+
+    /// Returns all effects of the plugin.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if communication with the `OpenRGB` SDK server fails.
+    pub async fn get_effects(&self) -> OpenRgbResult<Vec<PluginEffect>> {
+        self.proto
+            .effect_plugin_get_effects(self.data.index())
+            .await
+    }
+
+    /// Returns the effect with the given name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin has no effect with this name,
+    /// or communication with the `OpenRGB` SDK server fails.
+    pub async fn get_effect(&self, name: &str) -> OpenRgbResult<PluginEffect> {
+        self.get_effects()
+            .await?
+            .into_iter()
+            .find(|effect| effect.name() == name)
+            .ok_or_else(|| OpenRgbError::CommandError(format!("Effect {name} not found")))
+    }
+
+    /// Starts the effect with the given name.
+    ///
+    /// Other running effects keep running, see [`Self::stop_all_effects()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin has no effect with this name,
+    /// or communication with the `OpenRGB` SDK server fails.
+    pub async fn start_effect(&self, name: &str) -> OpenRgbResult<()> {
+        let effect = self.get_effect(name).await?;
+        self.proto
+            .effect_plugin_start_effect(self.data.index(), effect.name())
+            .await
+    }
+
+    /// Stops the effect with the given name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin has no effect with this name,
+    /// or communication with the `OpenRGB` SDK server fails.
+    pub async fn stop_effect(&self, name: &str) -> OpenRgbResult<()> {
+        let effect = self.get_effect(name).await?;
+        self.proto
+            .effect_plugin_stop_effect(self.data.index(), effect.name())
+            .await
+    }
+
+    /// Stops all running effects, and returns the effects that were stopped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if communication with the `OpenRGB` SDK server fails.
+    pub async fn stop_all_effects(&self) -> OpenRgbResult<Vec<PluginEffect>> {
+        let running = self
+            .get_effects()
+            .await?
+            .into_iter()
+            .filter(PluginEffect::enabled)
+            .collect::<Vec<_>>();
+        for effect in &running {
+            self.proto
+                .effect_plugin_stop_effect(self.data.index(), effect.name())
+                .await?;
+        }
+        Ok(running)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OpenRgbClient, OpenRgbResult};
+
+    #[tokio::test]
+    #[ignore = "can only test with openrgb running"]
+    async fn test_effects_plugin() -> OpenRgbResult<()> {
+        let client = OpenRgbClient::connect().await?;
+        let plugin = client.get_effects_plugin().await?;
+        let effects = plugin.get_effects().await?;
+        let Some(effect) = effects.first() else {
+            return Ok(());
+        };
+        plugin.start_effect(effect.name()).await?;
+        assert!(plugin.get_effect(effect.name()).await?.enabled());
+        let stopped = plugin.stop_all_effects().await?;
+        assert!(stopped.iter().any(|e| e.name() == effect.name()));
+        assert!(plugin.get_effects().await?.iter().all(|e| !e.enabled()));
+        assert!(plugin.start_effect("no such effect").await.is_err());
+        Ok(())
+    }
+}
@@ -1,8 +1,16 @@
-use crate::PluginData;
+mod effects_plugin;
 
+pub use effects_plugin::EffectsPlugin;
+
+use crate::{PluginData, protocol::OpenRgbProtocol};
+
+/// A plugin installed on `OpenRGB`, see [`crate::OpenRgbClient::get_plugins()`].
+#[derive(Debug, Clone)]
 pub enum OpenRgbPlugin {
-    // https://openrgb.org/plugin_effects.html
-    Effects,
+    /// <https://openrgb.org/plugin_effects.html>
+    ///
+    /// Argument is a handle to list, start and stop its effects.
+    Effects(EffectsPlugin),
     /// <https://openrgb.org/plugin_visual_map.html>
     VisualMap,
     /// <https://openrgb.org/plugin_hardware_sync.html>
@@ -19,10 +27,10 @@ pub enum OpenRgbPlugin {
     Unknown(String),
 }
 
-impl From<PluginData> for OpenRgbPlugin {
-    fn from(value: PluginData) -> Self {
-        match value.name() {
-            "OpenRGB Effects Plugin" => Self::Effects,
+impl OpenRgbPlugin {
+    pub(crate) fn new(proto: &OpenRgbProtocol, data: PluginData) -> Self {
+        match data.name() {
+            "OpenRGB Effects Plugin" => Self::Effects(EffectsPlugin::new(proto.clone(), data)),
             "Visual Map" => Self::VisualMap,
             "Hardware Sync" => Self::HardwareSync,
             "Fan Sync" => Self::FanSync,
@@ -31,12 +39,11 @@ impl From<PluginData> for OpenRgbPlugin {
             name => Self::Unknown(name.to_owned()),
         }
     }
-}
 
-impl OpenRgbPlugin {
+    /// Returns the name of this plugin.
     pub fn name(&self) -> &str {
         match self {
-            Self::Effects => "OpenRGB Effects Plugin",
+            Self::Effects(_) => "OpenRGB Effects Plugin",
             Self::VisualMap => "Visual Map",
             Self::HardwareSync => "Hardware Sync",
             Self::FanSync => "Fan Sync",
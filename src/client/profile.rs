@@ -0,0 +1,227 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ControllerGroup, ControllerState, OpenRgbResult, StateChange, StateSnapshot,
+    client::snapshot::restore_states,
+};
+
+/// Client side lighting profile, describing the mode, colors, zone sizes and segments per device.
+///
+/// Unlike the profiles stored by the `OpenRGB` server (see [`crate::OpenRgbClient::get_profiles()`]),
+/// a `Profile` can be inspected and edited, and is stored wherever the application wants.
+/// Devices are matched by their [`crate::DeviceId`], not by their index,
+/// see [`ControllerGroup::resolve()`].
+///
+/// With the `serde` feature profiles can be stored in any format, such as JSON.
+/// The `toml` feature adds [`Self::load()`] and [`Self::save()`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use openrgb2::*;
+/// # async fn example() -> OpenRgbResult<()> {
+/// let client = OpenRgbClient::connect().await?;
+/// let mut controllers = client.get_all_controllers().await?;
+/// let profile = Profile::capture(&controllers);
+/// // ...
+/// for device in profile.diff(&controllers).devices {
+///     for change in device.changes {
+///         println!("{}: {change}", device.name);
+///     }
+/// }
+/// profile.apply(&mut controllers).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Profile {
+    /// State per device.
+    #[cfg_attr(feature = "serde", serde(rename = "device", default))]
+    pub devices: Vec<ControllerState>,
+}
+
+impl Profile {
+    /// Captures the current state of all controllers in `group`.
+    ///
+    /// See [`StateSnapshot::capture()`].
+    pub fn capture(group: &ControllerGroup) -> Self {
+        Self {
+            devices: StateSnapshot::capture(group).controllers,
+        }
+    }
+
+    /// Returns what would change when applying this profile to `group`.
+    ///
+    /// Controllers that are already in the state of the profile are not included.
+    pub fn diff(&self, group: &ControllerGroup) -> ProfileDiff {
+        let mut diff = ProfileDiff::default();
+        let controllers = group.resolve(self.devices.iter().map(|d| &d.device));
+        for (device, controller) in self.devices.iter().zip(controllers) {
+            let Some(controller) = controller else {
+                diff.missing.push(device.device.name.clone());
+                continue;
+            };
+            let changes = device.diff(controller);
+            if !changes.is_empty() {
+                diff.devices.push(DeviceDiff {
+                    controller_id: controller.id(),
+                    name: device.device.name.clone(),
+                    changes,
+                });
+            }
+        }
+        diff
+    }
+
+    /// Applies this profile to `group`, only sending the packets needed to get to the state of the profile.
+    ///
+    /// Devices in the profile that are not in `group` are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error that occurred, see [`ControllerState::restore()`].
+    /// The remaining devices are still updated.
+    pub async fn apply(&self, group: &mut ControllerGroup) -> OpenRgbResult<()> {
+        restore_states(&self.devices, group).await
+    }
+
+    /// Parses a profile from a TOML string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a valid profile.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> OpenRgbResult<Self> {
+        super::toml_file::from_str(s)
+    }
+
+    /// Serializes this profile to a TOML string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile cannot be represented as TOML.
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> OpenRgbResult<String> {
+        super::toml_file::to_string(self)
+    }
+
+    /// Reads a profile from a TOML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid profile.
+    #[cfg(feature = "toml")]
+    pub fn load(path: impl AsRef<std::path::Path>) -> OpenRgbResult<Self> {
+        Self::from_toml(&super::toml_file::read(path.as_ref())?)
+    }
+
+    /// Writes this profile to a TOML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    #[cfg(feature = "toml")]
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> OpenRgbResult<()> {
+        super::toml_file::write(path.as_ref(), &self.to_toml()?)
+    }
+}
+
+/// Changes for a single device, see [`Profile::diff()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceDiff {
+    /// Id of the controller.
+    pub controller_id: usize,
+    /// Name of the device.
+    pub name: String,
+    /// Changes that applying the profile would make.
+    pub changes: Vec<StateChange>,
+}
+
+/// Result of [`Profile::diff()`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProfileDiff {
+    /// Devices that would change.
+    pub devices: Vec<DeviceDiff>,
+    /// Names of devices in the profile that were not found.
+    pub missing: Vec<String>,
+}
+
+impl ProfileDiff {
+    /// Returns true if applying the profile would not change anything.
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+}
+
+impl std::fmt::Display for ProfileDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for device in &self.devices {
+            writeln!(f, "{} ({}):", device.name, device.controller_id)?;
+            for change in &device.changes {
+                writeln!(f, "  - {change}")?;
+            }
+        }
+        for name in &self.missing {
+            writeln!(f, "{name}: not found")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OpenRgbClient;
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_from_toml() -> OpenRgbResult<()> {
+        use crate::{Color, ColorMode};
+
+        let profile = Profile::from_toml(
+            r#"
+            [[device]]
+            name = "Corsair Vengeance Pro RGB"
+            serial = ""
+            location = "I2C: /dev/i2c-1, address 0x58"
+            colors = [{ r = 255, g = 0, b = 0 }]
+
+            [device.mode]
+            id = 0
+            name = "Direct"
+            color_mode = "PerLED"
+            colors = []
+
+            [[device.zones]]
+            id = 0
+            name = "Corsair PRO Zone"
+            num_leds = 1
+            "#,
+        )?;
+        let device = &profile.devices[0];
+        assert_eq!(device.colors, [Color::new(255, 0, 0)]);
+        assert_eq!(device.mode.color_mode, ColorMode::PerLED);
+        assert_eq!(device.zones[0].segments, None);
+        assert_eq!(Profile::from_toml(&profile.to_toml()?)?, profile);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "can only test with openrgb running"]
+    async fn test_profile_diff() -> OpenRgbResult<()> {
+        let client = OpenRgbClient::connect().await?;
+        let mut controllers = client.get_all_controllers().await?;
+        let profile = Profile::capture(&controllers);
+        assert!(profile.diff(&controllers).is_empty());
+        controllers.turn_off_leds().await?;
+        for controller in controllers.controllers_mut() {
+            controller.sync_controller_data().await?;
+        }
+        println!("{}", profile.diff(&controllers));
+        profile.apply(&mut controllers).await?;
+        assert!(profile.diff(&controllers).is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use tokio::time::{Instant, sleep};
+
+use crate::{Controller, ControllerGroup, DeviceId, OpenRgbResult, client::device_id::assign};
+
+/// Time between two controller count polls while waiting for a rescan.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Number of polls without any change after which the device list is considered settled.
+const SETTLE_POLLS: usize = 5;
+
+/// A device that is present before and after a rescan, but changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceChange {
+    /// Identity of the device after the rescan.
+    pub device: DeviceId,
+    /// Controller id before the rescan.
+    pub old_id: usize,
+    /// Controller id after the rescan.
+    pub new_id: usize,
+    /// The device moved to another location, see [`crate::DeviceMatch::Serial`].
+    pub location_changed: bool,
+    /// The number of LEDs, or the names or sizes of the zones changed.
+    pub layout_changed: bool,
+    /// The available modes changed.
+    pub modes_changed: bool,
+}
+
+impl DeviceChange {
+    fn new(old: &Controller, new: &Controller) -> Option<Self> {
+        let zones = |c: &Controller| {
+            c.get_all_zones()
+                .map(|z| (z.name().to_owned(), z.num_leds()))
+                .collect::<Vec<_>>()
+        };
+        let modes = |c: &Controller| {
+            c.modes()
+                .iter()
+                .map(|m| m.name().to_owned())
+                .collect::<Vec<_>>()
+        };
+        let change = Self {
+            device: new.device_id(),
+            old_id: old.id(),
+            new_id: new.id(),
+            location_changed: old.location() != new.location(),
+            layout_changed: old.num_leds() != new.num_leds() || zones(old) != zones(new),
+            modes_changed: modes(old) != modes(new),
+        };
+        (change.id_changed()
+            || change.location_changed
+            || change.layout_changed
+            || change.modes_changed)
+            .then_some(change)
+    }
+
+    /// Returns true if the controller id of the device changed.
+    pub fn id_changed(&self) -> bool {
+        self.old_id != self.new_id
+    }
+}
+
+/// Difference between the controllers of the server before and after a rescan,
+/// see [`crate::OpenRgbClient::rescan_and_wait()`].
+///
+/// Devices are matched by their [`DeviceId`], so a device that only got another controller id
+/// is reported as changed, not as removed and added.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceListDiff {
+    /// Devices that were not present before the rescan.
+    pub added: Vec<DeviceId>,
+    /// Devices that are no longer present after the rescan.
+    pub removed: Vec<DeviceId>,
+    /// Devices that are still present, but changed.
+    pub changed: Vec<DeviceChange>,
+}
+
+impl DeviceListDiff {
+    /// Compares the controllers of `old` and `new`.
+    pub fn between(old: &ControllerGroup, new: &ControllerGroup) -> Self {
+        let old_ids = old.iter().map(Controller::device_id).collect::<Vec<_>>();
+        let new_controllers = new.iter().collect::<Vec<_>>();
+        let new_ids = new.iter().map(Controller::device_id).collect::<Vec<_>>();
+        let assigned = assign(&old_ids.iter().collect::<Vec<_>>(), &new_ids);
+
+        let mut diff = Self::default();
+        let mut matched = vec![false; new_ids.len()];
+        for ((old_id, old_controller), pos) in old_ids.into_iter().zip(old).zip(assigned) {
+            match pos {
+                Some(pos) => {
+                    matched[pos] = true;
+                    diff.changed
+                        .extend(DeviceChange::new(old_controller, new_controllers[pos]));
+                }
+                None => diff.removed.push(old_id),
+            }
+        }
+        diff.added = new_ids
+            .into_iter()
+            .zip(matched)
+            .filter_map(|(id, matched)| (!matched).then_some(id))
+            .collect();
+        diff
+    }
+
+    /// Returns true if the device list did not change.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Polls until the device list settles after a rescan, or `timeout` passes.
+///
+/// `poll` returns the current controller count and whether a device list update was notified.
+/// The list is settled once something changed, followed by [`SETTLE_POLLS`] polls without change.
+/// Returns false if the list did not settle in time.
+pub(crate) async fn wait_for_device_list<F, Fut>(
+    mut count: usize,
+    timeout: Duration,
+    mut poll: F,
+) -> OpenRgbResult<bool>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = OpenRgbResult<(usize, bool)>>,
+{
+    let deadline = Instant::now() + timeout;
+    let mut changed = false;
+    let mut stable = 0;
+    while Instant::now() < deadline {
+        sleep(POLL_INTERVAL.min(deadline - Instant::now())).await;
+        let (new_count, updated) = poll().await?;
+        if updated || new_count != count {
+            changed = true;
+            stable = 0;
+            count = new_count;
+        } else if changed {
+            stable += 1;
+            if stable >= SETTLE_POLLS {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OpenRgbClient;
+
+    #[tokio::test]
+    async fn test_wait_for_device_list() -> OpenRgbResult<()> {
+        // the server empties its list, then detects the devices one by one
+        let mut counts = [3, 0, 1, 2, 3].into_iter();
+        let settled = wait_for_device_list(3, Duration::from_secs(5), || {
+            let count = counts.next().unwrap_or(3);
+            async move { Ok((count, false)) }
+        })
+        .await?;
+        assert!(settled);
+        assert_eq!(counts.len(), 0);
+
+        // the count is the same, but the server notified an update
+        let mut updates = [false, true].into_iter();
+        let settled = wait_for_device_list(3, Duration::from_secs(5), || {
+            let updated = updates.next().unwrap_or(false);
+            async move { Ok((3, updated)) }
+        })
+        .await?;
+        assert!(settled);
+
+        // nothing happens
+        let start = Instant::now();
+        let settled =
+            wait_for_device_list(3, Duration::from_millis(250), || async { Ok((3, false)) })
+                .await?;
+        assert!(!settled);
+        assert!(start.elapsed() < Duration::from_secs(1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "can only test with openrgb running"]
+    async fn test_rescan_and_wait() -> OpenRgbResult<()> {
+        let client = OpenRgbClient::connect().await?;
+        let before = client.get_all_controllers().await?;
+        let (after, diff) = client.rescan_and_wait(Duration::from_secs(30)).await?;
+        assert_eq!(
+            before.len() + diff.added.len() - diff.removed.len(),
+            after.len()
+        );
+        assert!(DeviceListDiff::between(&after, &after).is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::{Controller, DeviceId, ModeData, ModeFlag, OpenRgbError, OpenRgbResult};
+
+/// Outcome of [`SaveGuard::save()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveOutcome {
+    /// The mode was written to the flash memory of the controller.
+    Saved,
+    /// The mode is identical to the last saved mode, nothing was written.
+    Unchanged,
+    /// The controller was saved too recently, nothing was written.
+    RateLimited {
+        /// Time until the controller can be saved again.
+        retry_after: Duration,
+    },
+}
+
+/// Protects controllers against flash wear caused by saving modes too often.
+///
+/// [`Controller::save_mode()`] writes to the flash memory of the device every time it is called.
+/// A `SaveGuard` only saves if:
+///
+/// - the active mode supports [`ModeFlag::ManualSave`],
+/// - the mode differs from the last mode this guard saved for that controller,
+/// - at least the minimum interval has passed since the last save of that controller.
+///
+/// It also counts the number of writes per controller, see [`Self::writes()`].
+/// Use a single guard for all saves in an application, controllers are tracked by their [`DeviceId`],
+/// so the history of a device is kept when a rescan gives it another controller id.
+///
+/// # Example
+///
+/// ```no_run
+/// # use openrgb2::*;
+/// # use std::time::Duration;
+/// # async fn example(controller: &Controller) -> OpenRgbResult<()> {
+/// let guard = SaveGuard::new(Duration::from_secs(300));
+/// match guard.save(controller).await? {
+///     SaveOutcome::Saved => println!("saved, {} writes so far", guard.writes(controller)),
+///     SaveOutcome::Unchanged => println!("already saved"),
+///     SaveOutcome::RateLimited { retry_after } => println!("try again in {retry_after:?}"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SaveGuard {
+    min_interval: Duration,
+    state: Mutex<HashMap<DeviceId, SaveState<ModeData>>>,
+}
+
+impl Default for SaveGuard {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MIN_INTERVAL)
+    }
+}
+
+impl SaveGuard {
+    /// Default minimum time between two saves of the same controller.
+    pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(60);
+
+    /// Creates a guard that saves a controller at most once every `min_interval`.
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            state: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the minimum time between two saves of the same controller.
+    pub fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+
+    /// Saves the active mode of `controller` if it is allowed, see [`SaveGuard`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the active mode does not support [`ModeFlag::ManualSave`],
+    /// or if saving fails.
+    pub async fn save(&self, controller: &Controller) -> OpenRgbResult<SaveOutcome> {
+        let mode = controller.active_mode().into_data();
+        if !mode.flags().contains(ModeFlag::ManualSave) {
+            return Err(OpenRgbError::CommandError(format!(
+                "Controller {} mode {} cannot be saved",
+                controller.name(),
+                mode.name()
+            )));
+        }
+
+        // reserve the save before writing, so concurrent saves of the same controller are limited too
+        let device = controller.device_id();
+        let now = Instant::now();
+        let previous = match self.reserve(device.clone(), mode, now) {
+            Ok(previous) => previous,
+            Err(outcome) => return Ok(outcome),
+        };
+
+        if let Err(e) = controller.save_mode().await {
+            if let Some(entry) = self.lock().get_mut(&device) {
+                entry.rollback(previous, mode, now);
+            }
+            return Err(e);
+        }
+        Ok(SaveOutcome::Saved)
+    }
+
+    /// Records a save of `mode` if it is allowed, returning the previous state of the controller.
+    fn reserve(
+        &self,
+        device: DeviceId,
+        mode: &ModeData,
+        now: Instant,
+    ) -> Result<SaveState<ModeData>, SaveOutcome> {
+        let mut state = self.lock();
+        let entry = state.entry(device).or_default();
+        let reserved = match entry.check(mode, now, self.min_interval) {
+            Some(outcome) => Err(outcome),
+            None => Ok(entry.record(mode.clone(), now)),
+        };
+        drop(state);
+        reserved
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<DeviceId, SaveState<ModeData>>> {
+        self.state.lock().expect("SaveGuard lock poisoned")
+    }
+
+    /// Returns the number of times this guard wrote to the flash memory of `controller`.
+    pub fn writes(&self, controller: &Controller) -> u64 {
+        self.lock()
+            .get(&controller.device_id())
+            .map_or(0, |s| s.writes)
+    }
+
+    /// Returns the number of flash writes per device.
+    pub fn all_writes(&self) -> HashMap<DeviceId, u64> {
+        self.lock()
+            .iter()
+            .map(|(device, s)| (device.clone(), s.writes))
+            .collect()
+    }
+
+    /// Forgets the last saved mode of `controller`, so the next save is not skipped as unchanged.
+    ///
+    /// The write counter and rate limit are kept.
+    pub fn forget(&self, controller: &Controller) {
+        if let Some(entry) = self.lock().get_mut(&controller.device_id()) {
+            entry.last_saved = None;
+        }
+    }
+}
+
+/// Save history of a single controller.
+#[derive(Debug, Clone)]
+struct SaveState<M> {
+    last_saved: Option<M>,
+    last_save: Option<Instant>,
+    writes: u64,
+}
+
+impl<M> Default for SaveState<M> {
+    fn default() -> Self {
+        Self {
+            last_saved: None,
+            last_save: None,
+            writes: 0,
+        }
+    }
+}
+
+impl<M: PartialEq + Clone> SaveState<M> {
+    /// Returns why `mode` should not be saved at `now`, or `None` if it can be saved.
+    fn check(&self, mode: &M, now: Instant, min_interval: Duration) -> Option<SaveOutcome> {
+        if self.last_saved.as_ref() == Some(mode) {
+            return Some(SaveOutcome::Unchanged);
+        }
+        let elapsed = now.saturating_duration_since(self.last_save?);
+        (elapsed < min_interval).then(|| SaveOutcome::RateLimited {
+            retry_after: min_interval - elapsed,
+        })
+    }
+
+    /// Records a save of `mode` at `now`, returning the previous state.
+    fn record(&mut self, mode: M, now: Instant) -> Self {
+        let previous = self.clone();
+        self.last_saved = Some(mode);
+        self.last_save = Some(now);
+        self.writes += 1;
+        previous
+    }
+
+    /// Undoes a failed save of `mode` recorded at `now`,
+    /// where `previous` is the state returned by [`Self::record()`].
+    ///
+    /// Saves recorded in the meantime are kept.
+    fn rollback(&mut self, previous: Self, mode: &M, now: Instant) {
+        self.writes = self.writes.saturating_sub(1);
+        if self.last_saved.as_ref() == Some(mode) && self.last_save == Some(now) {
+            self.last_saved = previous.last_saved;
+            self.last_save = previous.last_save;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_state() {
+        let interval = Duration::from_secs(60);
+        let start = Instant::now();
+        let mut state = SaveState::default();
+
+        assert_eq!(state.check(&1, start, interval), None);
+        state.record(1, start);
+        assert_eq!(
+            state.check(&1, start, interval),
+            Some(SaveOutcome::Unchanged)
+        );
+        assert_eq!(
+            state.check(&2, start + Duration::from_secs(20), interval),
+            Some(SaveOutcome::RateLimited {
+                retry_after: Duration::from_secs(40)
+            })
+        );
+
+        let later = start + interval;
+        assert_eq!(state.check(&2, later, interval), None);
+        let previous = state.record(2, later);
+        assert_eq!(state.writes, 2);
+        // identical modes are skipped regardless of time
+        assert_eq!(
+            state.check(&2, later + interval * 10, interval),
+            Some(SaveOutcome::Unchanged)
+        );
+
+        // a failed save restores the previous state
+        state.rollback(previous, &2, later);
+        assert_eq!(state.writes, 1);
+        assert_eq!(state.check(&2, later, interval), None);
+
+        // a save that succeeded while another one was in flight is kept
+        let failed = state.record(3, later);
+        let last = later + interval;
+        state.record(4, last);
+        state.rollback(failed, &3, later);
+        assert_eq!(state.writes, 2);
+        assert_eq!(state.last_saved, Some(4));
+        assert_eq!(state.last_save, Some(last));
+    }
+}
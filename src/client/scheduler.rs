@@ -0,0 +1,362 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::{CommandGroup, ControllerGroup, OpenRgbError, OpenRgbResult};
+
+/// Number of write latency samples kept for the percentile statistics.
+const LATENCY_WINDOW: usize = 1024;
+
+/// Drives a render closure at a fixed frame rate.
+///
+/// Frame deadlines are computed from the moment the scheduler starts running,
+/// so small delays do not accumulate into drift over time.
+/// When a frame takes longer than its slot (e.g. because the server is slow),
+/// the ticks that were missed are skipped instead of being rendered in a burst.
+///
+/// # Example
+///
+/// ```no_run
+/// # use openrgb2::{Color, FrameScheduler, OpenRgbClient, OpenRgbResult};
+/// # use std::time::Duration;
+/// # async fn example() -> OpenRgbResult<()> {
+/// let client = OpenRgbClient::connect().await?;
+/// let group = client.get_all_controllers().await?;
+/// group.init().await?;
+///
+/// let mut scheduler = FrameScheduler::new(60)?;
+/// scheduler
+///     .run_for(&group, Duration::from_secs(10), |group, _t| {
+///         let mut cmd = group.cmd();
+///         for c in group {
+///             let _ = cmd.set_controller_leds(c, vec![Color::new(255, 0, 0); c.num_leds()]);
+///         }
+///         cmd
+///     })
+///     .await?;
+/// println!("achieved {:.1} FPS", scheduler.stats().achieved_fps());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FrameScheduler {
+    frame_time: Duration,
+    stats: FrameStats,
+}
+
+impl FrameScheduler {
+    /// Creates a scheduler that targets `fps` frames per second.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `fps` is 0.
+    pub fn new(fps: u32) -> OpenRgbResult<Self> {
+        if fps == 0 {
+            return Err(OpenRgbError::CommandError(
+                "Frame rate must be greater than 0".to_owned(),
+            ));
+        }
+        Ok(Self::with_frame_time(Duration::from_secs(1) / fps))
+    }
+
+    /// Creates a scheduler that renders a frame every `frame_time`.
+    ///
+    /// A `frame_time` of zero renders frames as fast as the server accepts them.
+    pub fn with_frame_time(frame_time: Duration) -> Self {
+        Self {
+            frame_time,
+            stats: FrameStats::default(),
+        }
+    }
+
+    /// Returns the time between two frames.
+    pub fn frame_time(&self) -> Duration {
+        self.frame_time
+    }
+
+    /// Returns the frame rate this scheduler is targeting.
+    pub fn target_fps(&self) -> f64 {
+        match self.frame_time.is_zero() {
+            true => f64::INFINITY,
+            false => 1.0 / self.frame_time.as_secs_f64(),
+        }
+    }
+
+    /// Returns the statistics of the frames rendered so far.
+    pub fn stats(&self) -> &FrameStats {
+        &self.stats
+    }
+
+    /// Clears the statistics collected so far.
+    pub fn reset_stats(&mut self) {
+        self.stats = FrameStats::default();
+    }
+
+    /// Renders frames until writing a frame fails.
+    ///
+    /// `render` is called with the group and the scheduled time of the frame,
+    /// the returned [`CommandGroup`] is then executed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered while executing a frame.
+    pub async fn run<F>(&mut self, group: &ControllerGroup, render: F) -> OpenRgbResult<()>
+    where
+        F: for<'a> FnMut(&'a ControllerGroup, Instant) -> CommandGroup<'a>,
+    {
+        self.run_while(group, render, |_, _| true).await
+    }
+
+    /// Renders frames for the given `duration`.
+    ///
+    /// See [`Self::run()`].
+    pub async fn run_for<F>(
+        &mut self,
+        group: &ControllerGroup,
+        duration: Duration,
+        render: F,
+    ) -> OpenRgbResult<()>
+    where
+        F: for<'a> FnMut(&'a ControllerGroup, Instant) -> CommandGroup<'a>,
+    {
+        self.run_while(group, render, |elapsed, _| elapsed < duration)
+            .await
+    }
+
+    /// Renders `frames` frames, not counting frames that were skipped.
+    ///
+    /// See [`Self::run()`].
+    pub async fn run_frames<F>(
+        &mut self,
+        group: &ControllerGroup,
+        frames: u64,
+        render: F,
+    ) -> OpenRgbResult<()>
+    where
+        F: for<'a> FnMut(&'a ControllerGroup, Instant) -> CommandGroup<'a>,
+    {
+        self.run_while(group, render, |_, rendered| rendered < frames)
+            .await
+    }
+
+    /// Main loop, `keep_running` gets the time since start and the number of rendered frames.
+    async fn run_while<F, K>(
+        &mut self,
+        group: &ControllerGroup,
+        mut render: F,
+        keep_running: K,
+    ) -> OpenRgbResult<()>
+    where
+        F: for<'a> FnMut(&'a ControllerGroup, Instant) -> CommandGroup<'a>,
+        K: Fn(Duration, u64) -> bool,
+    {
+        let start = Instant::now();
+        let mut tick: u64 = 0;
+        let mut rendered: u64 = 0;
+        self.stats.start(start);
+
+        while keep_running(start.elapsed(), rendered) {
+            let deadline = start + self.tick_offset(tick);
+            tokio::time::sleep_until(deadline.into()).await;
+
+            let cmd = render(group, deadline);
+            let write_start = Instant::now();
+            cmd.execute().await?;
+            let done = Instant::now();
+            rendered += 1;
+
+            let next_tick = self.next_tick(done - start).max(tick + 1);
+            let late = !self.frame_time.is_zero() && done > start + self.tick_offset(tick + 1);
+            self.stats
+                .record_frame(done, done - write_start, late, next_tick - tick - 1);
+            tick = next_tick;
+        }
+        Ok(())
+    }
+
+    /// Offset of the given tick relative to the start.
+    fn tick_offset(&self, tick: u64) -> Duration {
+        let nanos = self.frame_time.as_nanos().saturating_mul(u128::from(tick));
+        Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+    }
+
+    /// First tick whose deadline has not passed yet after `elapsed`.
+    fn next_tick(&self, elapsed: Duration) -> u64 {
+        if self.frame_time.is_zero() {
+            return 0;
+        }
+        let tick = elapsed.as_nanos() / self.frame_time.as_nanos() + 1;
+        u64::try_from(tick).unwrap_or(u64::MAX)
+    }
+}
+
+/// Statistics collected by a [`FrameScheduler`].
+#[derive(Debug, Clone, Default)]
+pub struct FrameStats {
+    frames: u64,
+    late_frames: u64,
+    dropped_frames: u64,
+    /// Time spent in runs before the current one, up to their last frame.
+    previous_runs: Duration,
+    run_started: Option<Instant>,
+    last_frame: Option<Instant>,
+    latencies: VecDeque<Duration>,
+}
+
+impl FrameStats {
+    /// Starts a new run, so the time between runs does not count for [`Self::achieved_fps()`].
+    fn start(&mut self, now: Instant) {
+        self.previous_runs += self.current_run();
+        self.run_started = Some(now);
+        self.last_frame = None;
+    }
+
+    /// Time from the start of the current run to its last frame.
+    fn current_run(&self) -> Duration {
+        match (self.run_started, self.last_frame) {
+            (Some(started), Some(last_frame)) => last_frame.saturating_duration_since(started),
+            _ => Duration::ZERO,
+        }
+    }
+
+    fn record_frame(&mut self, now: Instant, latency: Duration, late: bool, dropped: u64) {
+        self.frames += 1;
+        self.dropped_frames += dropped;
+        if late {
+            self.late_frames += 1;
+        }
+        self.last_frame = Some(now);
+        if self.latencies.len() == LATENCY_WINDOW {
+            self.latencies.pop_front();
+        }
+        self.latencies.push_back(latency);
+    }
+
+    /// Returns the number of frames that were written.
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Returns the number of frames that finished after the next frame should have started.
+    pub fn late_frames(&self) -> u64 {
+        self.late_frames
+    }
+
+    /// Returns the number of frames that were skipped because a previous frame was late.
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped_frames
+    }
+
+    /// Returns the average number of frames written per second.
+    ///
+    /// Only the time spent running counts, not the time between runs.
+    pub fn achieved_fps(&self) -> f64 {
+        let elapsed = (self.previous_runs + self.current_run()).as_secs_f64();
+        match elapsed > 0.0 {
+            true => self.frames as f64 / elapsed,
+            false => 0.0,
+        }
+    }
+
+    /// Returns the write latency at percentile `p` (between 0.0 and 100.0).
+    ///
+    /// Only the most recent 1024 frames are taken into account.
+    /// Returns `None` if no frames were written yet.
+    pub fn latency_percentile(&self, p: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let mut sorted = self.latencies.iter().copied().collect::<Vec<_>>();
+        sorted.sort_unstable();
+        let rank = (p.clamp(0.0, 100.0) / 100.0 * (sorted.len() - 1) as f64).round() as usize;
+        sorted.get(rank).copied()
+    }
+
+    /// Returns the median write latency.
+    pub fn median_latency(&self) -> Option<Duration> {
+        self.latency_percentile(50.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_fps() {
+        assert!(FrameScheduler::new(0).is_err());
+        let scheduler = FrameScheduler::new(50).unwrap();
+        assert_eq!(scheduler.frame_time(), Duration::from_millis(20));
+        assert!((scheduler.target_fps() - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_next_tick() {
+        let scheduler = FrameScheduler::with_frame_time(Duration::from_millis(10));
+        assert_eq!(scheduler.next_tick(Duration::from_millis(3)), 1);
+        assert_eq!(scheduler.next_tick(Duration::from_millis(10)), 2);
+        assert_eq!(scheduler.next_tick(Duration::from_millis(35)), 4);
+        assert_eq!(scheduler.tick_offset(4), Duration::from_millis(40));
+    }
+
+    #[test]
+    fn test_stats() {
+        let start = Instant::now();
+        let mut stats = FrameStats::default();
+        assert_eq!(stats.latency_percentile(50.0), None);
+        assert!(stats.achieved_fps().abs() < f64::EPSILON);
+
+        stats.start(start);
+        for i in 1..=10 {
+            let late = i == 10;
+            let dropped = u64::from(late) * 2;
+            stats.record_frame(
+                start + Duration::from_millis(100 * i),
+                Duration::from_millis(i),
+                late,
+                dropped,
+            );
+        }
+        assert_eq!(stats.frames(), 10);
+        assert_eq!(stats.late_frames(), 1);
+        assert_eq!(stats.dropped_frames(), 2);
+        assert!((stats.achieved_fps() - 10.0).abs() < 1e-9);
+        assert_eq!(
+            stats.latency_percentile(0.0),
+            Some(Duration::from_millis(1))
+        );
+        assert_eq!(
+            stats.latency_percentile(100.0),
+            Some(Duration::from_millis(10))
+        );
+        assert_eq!(stats.median_latency(), Some(Duration::from_millis(6)));
+
+        // a second run after an idle minute
+        let second = start + Duration::from_secs(60);
+        stats.start(second);
+        assert!((stats.achieved_fps() - 10.0).abs() < 1e-9);
+        for i in 1..=10 {
+            stats.record_frame(
+                second + Duration::from_millis(50 * i),
+                Duration::from_millis(1),
+                false,
+                0,
+            );
+        }
+        assert_eq!(stats.frames(), 20);
+        assert!((stats.achieved_fps() - 20.0 / 1.5).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    #[ignore = "can only test with openrgb running"]
+    async fn test_run_frames() -> OpenRgbResult<()> {
+        let client = crate::OpenRgbClient::connect().await?;
+        let group = client.get_all_controllers().await?;
+        group.set_controllable_mode().await?;
+        let mut scheduler = FrameScheduler::new(60)?;
+        scheduler
+            .run_frames(&group, 120, |group, _| group.cmd())
+            .await?;
+        println!("{:#?}", scheduler.stats());
+        Ok(())
+    }
+}
@@ -73,25 +73,17 @@ impl<'c> Segment<'c> {
 
     /// Sets all LEDs in this segment to the given `color`.
     ///
-    /// # Limitation
-    ///
-    /// This will set every other LED in the zone to black, as those colors are not specified.
-    /// To get around this, use `[Self::cmd()]` instead and specify the zone color.
-    /// See the `segment.rs` example for an example
+    /// The other LEDs in the zone keep their known color, see [`Self::set_leds()`].
     pub async fn set_all_leds<C: Into<Color>>(&self, color: C) -> OpenRgbResult<()> {
         let color = color.into();
-        let colors = (0..self.num_leds()).map(|_| color);
-        let seg_colors = self.prepend_colors(colors);
-        self.zone.set_leds(seg_colors).await
+        self.write_merged(&vec![color; self.num_leds()]).await
     }
 
     /// Sets the LEDs in this segment to the given colors.
     ///
-    /// # Limitation
-    ///
-    /// This will set every other LED in the zone to black, as those colors are not specified.
-    /// To get around this, use `[Self::cmd()]` instead and specify the zone color.
-    /// See the `segment.rs` example for an example
+    /// The colors are merged into the known colors of the zone (see [`Zone::known_colors()`])
+    /// and sent as a single zone update, so the other LEDs in the zone keep their color.
+    /// This allows animating multiple segments of the same zone independently.
     pub async fn set_leds<C: Into<Color>>(
         &self,
         colors: impl IntoIterator<Item = C>,
@@ -106,16 +98,18 @@ impl<'c> Segment<'c> {
                 self.num_leds()
             )
         }
-        let seg_colors = self.prepend_colors(color_v);
-        self.zone.set_leds(seg_colors).await
+        self.write_merged(&color_v).await
     }
 
-    fn prepend_colors<C: Into<Color>>(
-        &self,
-        colors: impl IntoIterator<Item = C>,
-    ) -> impl Iterator<Item = Color> {
-        let color_v = colors.into_iter().map(Into::into);
-        (0..self.offset()).map(|_| Color::default()).chain(color_v)
+    /// Writes `colors` to the start of this segment, keeping the known colors of the rest of the zone.
+    ///
+    /// Colors beyond the end of this segment are ignored.
+    async fn write_merged(&self, colors: &[Color]) -> OpenRgbResult<()> {
+        let len = colors.len().min(self.num_leds());
+        self.zone
+            .controller()
+            .set_zone_range(self.zone_id(), self.offset(), &colors[..len])
+            .await
     }
 
     /// Creates a new [`Command`] for the controller of this segment's zone.
@@ -0,0 +1,477 @@
+use std::fmt;
+
+use crate::{Controller, OpenRgbError, OpenRgbResult, SegmentState, protocol::OpenRgbProtocol};
+
+/// Edits the segments of a controller in memory and writes them back at once.
+///
+/// The SDK can only add segments or clear all segments of a controller,
+/// so editing a single segment means recreating all of them.
+/// A `SegmentManager` reads the current segments of every zone, lets you change them,
+/// validates the result and then replays the complete layout with [`Self::apply()`].
+///
+/// Segments are addressed by zone id and their index in the zone.
+/// Requires protocol version 5.
+///
+/// # Example
+///
+/// ```no_run
+/// # use openrgb2::*;
+/// # async fn example(controller: &mut Controller) -> OpenRgbResult<()> {
+/// let mut segments = controller.segment_manager()?;
+/// segments.rename(0, 0, "Front")?;
+/// segments.resize(0, 1, 10, 20)?;
+/// segments.push(
+///     0,
+///     SegmentState {
+///         name: "Back".to_owned(),
+///         offset: 30,
+///         num_leds: 10,
+///         seg_type: ZoneType::Linear,
+///     },
+/// )?;
+/// for issue in segments.issues() {
+///     println!("{issue}");
+/// }
+/// segments.apply().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SegmentManager<'c> {
+    controller: &'c mut Controller,
+    zone_leds: Vec<usize>,
+    original: Vec<Vec<SegmentState>>,
+    zones: Vec<Vec<SegmentState>>,
+}
+
+impl<'c> SegmentManager<'c> {
+    pub(crate) fn new(controller: &'c mut Controller) -> OpenRgbResult<Self> {
+        controller
+            .proto()
+            .check_protocol_version(5, "Segment management")?;
+        let zone_leds = controller.get_all_zones().map(|z| z.num_leds()).collect();
+        let zones = segment_states(controller);
+        Ok(Self {
+            controller,
+            zone_leds,
+            original: zones.clone(),
+            zones,
+        })
+    }
+
+    /// Returns the segments of the zone with id `zone_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the zone does not exist.
+    pub fn zone_segments(&self, zone_id: usize) -> OpenRgbResult<&[SegmentState]> {
+        self.zones
+            .get(zone_id)
+            .map(Vec::as_slice)
+            .ok_or_else(|| self.zone_not_found(zone_id))
+    }
+
+    fn zone_mut(&mut self, zone_id: usize) -> OpenRgbResult<&mut Vec<SegmentState>> {
+        let err = self.zone_not_found(zone_id);
+        self.zones.get_mut(zone_id).ok_or(err)
+    }
+
+    fn segment_mut(&mut self, zone_id: usize, index: usize) -> OpenRgbResult<&mut SegmentState> {
+        self.zone_mut(zone_id)?
+            .get_mut(index)
+            .ok_or_else(|| segment_not_found(zone_id, index))
+    }
+
+    fn zone_not_found(&self, zone_id: usize) -> OpenRgbError {
+        OpenRgbError::CommandError(format!(
+            "Zone {zone_id} not found in controller {}",
+            self.controller.name()
+        ))
+    }
+
+    /// Renames the segment at `index` in zone `zone_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the segment does not exist.
+    pub fn rename(
+        &mut self,
+        zone_id: usize,
+        index: usize,
+        name: impl Into<String>,
+    ) -> OpenRgbResult<()> {
+        self.segment_mut(zone_id, index)?.name = name.into();
+        Ok(())
+    }
+
+    /// Moves the segment at `index` in zone `zone_id` to start at `offset` with `num_leds` LEDs.
+    ///
+    /// The new range is validated by [`Self::issues()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the segment does not exist.
+    pub fn resize(
+        &mut self,
+        zone_id: usize,
+        index: usize,
+        offset: usize,
+        num_leds: usize,
+    ) -> OpenRgbResult<()> {
+        let segment = self.segment_mut(zone_id, index)?;
+        segment.offset = offset;
+        segment.num_leds = num_leds;
+        Ok(())
+    }
+
+    /// Removes the segment at `index` from zone `zone_id` and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the segment does not exist.
+    pub fn remove(&mut self, zone_id: usize, index: usize) -> OpenRgbResult<SegmentState> {
+        let zone = self.zone_mut(zone_id)?;
+        if index >= zone.len() {
+            return Err(segment_not_found(zone_id, index));
+        }
+        Ok(zone.remove(index))
+    }
+
+    /// Inserts `segment` at `index` in zone `zone_id`, shifting later segments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the zone does not exist or `index` is larger than the number of segments.
+    pub fn insert(
+        &mut self,
+        zone_id: usize,
+        index: usize,
+        segment: SegmentState,
+    ) -> OpenRgbResult<()> {
+        let zone = self.zone_mut(zone_id)?;
+        if index > zone.len() {
+            return Err(segment_not_found(zone_id, index));
+        }
+        zone.insert(index, segment);
+        Ok(())
+    }
+
+    /// Appends `segment` to the segments of zone `zone_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the zone does not exist.
+    pub fn push(&mut self, zone_id: usize, segment: SegmentState) -> OpenRgbResult<()> {
+        self.zone_mut(zone_id)?.push(segment);
+        Ok(())
+    }
+
+    /// Moves the segment at index `from` to index `to` in zone `zone_id`.
+    ///
+    /// This changes the order (and ids) of the segments, not the LEDs they cover.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either index is out of bounds.
+    pub fn reorder(&mut self, zone_id: usize, from: usize, to: usize) -> OpenRgbResult<()> {
+        let zone = self.zone_mut(zone_id)?;
+        if let Some(index) = [from, to].into_iter().find(|idx| *idx >= zone.len()) {
+            return Err(segment_not_found(zone_id, index));
+        }
+        let segment = zone.remove(from);
+        zone.insert(to, segment);
+        Ok(())
+    }
+
+    /// Removes all segments of zone `zone_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the zone does not exist.
+    pub fn clear_zone(&mut self, zone_id: usize) -> OpenRgbResult<()> {
+        self.zone_mut(zone_id)?.clear();
+        Ok(())
+    }
+
+    /// Replaces the segments of all zones, `zones` has the segments per zone id.
+    pub(crate) fn set_all(&mut self, zones: Vec<Vec<SegmentState>>) {
+        self.zones = zones;
+    }
+
+    /// Returns true if the segments differ from the segments of the controller.
+    pub fn is_changed(&self) -> bool {
+        self.zones != self.original
+    }
+
+    /// Returns the problems that prevent the segments from being applied.
+    pub fn issues(&self) -> Vec<SegmentIssue> {
+        validate(&self.zone_leds, &self.zones)
+    }
+
+    /// Writes the segments to the controller and resyncs its controller data.
+    ///
+    /// All segments of the controller are cleared and added again in order.
+    /// Nothing is sent if the segments did not change.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if [`Self::issues()`] is not empty, or communication with the server fails.
+    pub async fn apply(self) -> OpenRgbResult<()> {
+        let issues = self.issues();
+        if !issues.is_empty() {
+            let issues = issues.iter().map(ToString::to_string).collect::<Vec<_>>();
+            return Err(OpenRgbError::CommandError(format!(
+                "Invalid segments for controller {}: {}",
+                self.controller.name(),
+                issues.join(", ")
+            )));
+        }
+        if !self.is_changed() {
+            return Ok(());
+        }
+        let zones = self.zones.iter().map(Vec::as_slice).enumerate();
+        let result = replace_segments(self.controller.proto(), self.controller.id(), zones).await;
+        // resync even if a segment failed, so the controller data matches the server
+        let synced = self.controller.sync_controller_data().await;
+        result.and(synced)
+    }
+}
+
+/// Problem with the segments of a [`SegmentManager`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SegmentIssue {
+    /// The segment has no LEDs.
+    Empty {
+        /// Id of the zone.
+        zone_id: usize,
+        /// Name of the segment.
+        segment: String,
+    },
+    /// The segment ends after the last LED of the zone.
+    OutOfZone {
+        /// Id of the zone.
+        zone_id: usize,
+        /// Name of the segment.
+        segment: String,
+        /// Index after the last LED of the segment.
+        end: usize,
+        /// Number of LEDs in the zone.
+        zone_leds: usize,
+    },
+    /// Two segments share LEDs.
+    Overlap {
+        /// Id of the zone.
+        zone_id: usize,
+        /// Name of the first segment.
+        first: String,
+        /// Name of the second segment.
+        second: String,
+    },
+}
+
+impl fmt::Display for SegmentIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty { zone_id, segment } => {
+                write!(f, "segment {segment} in zone {zone_id} has no LEDs")
+            }
+            Self::OutOfZone {
+                zone_id,
+                segment,
+                end,
+                zone_leds,
+            } => write!(
+                f,
+                "segment {segment} ends at LED {end}, but zone {zone_id} has {zone_leds} LEDs"
+            ),
+            Self::Overlap {
+                zone_id,
+                first,
+                second,
+            } => write!(f, "segments {first} and {second} in zone {zone_id} overlap"),
+        }
+    }
+}
+
+/// Returns the segments of every zone of `controller`, indexed by zone id.
+pub(crate) fn segment_states(controller: &Controller) -> Vec<Vec<SegmentState>> {
+    controller
+        .get_all_zones()
+        .map(|zone| {
+            zone.segment_data()
+                .unwrap_or_default()
+                .iter()
+                .map(SegmentState::capture)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Removes the segments of zone `zone_id` in `zones` that do not fit in `zone_leds` LEDs.
+///
+/// Returns the removed segments, in order.
+pub(crate) fn prune_zone_segments(
+    zones: &mut [Vec<SegmentState>],
+    zone_id: usize,
+    zone_leds: usize,
+) -> Vec<SegmentState> {
+    let Some(segments) = zones.get_mut(zone_id) else {
+        return Vec::new();
+    };
+    let (kept, removed) = std::mem::take(segments)
+        .into_iter()
+        .partition(|s| s.offset + s.num_leds <= zone_leds);
+    *segments = kept;
+    removed
+}
+
+/// Returns the problems with `zones`, the segments per zone id, for zones of `zone_leds` LEDs.
+fn validate(zone_leds: &[usize], zones: &[Vec<SegmentState>]) -> Vec<SegmentIssue> {
+    let mut issues = Vec::new();
+    for (zone_id, (segments, &zone_leds)) in zones.iter().zip(zone_leds).enumerate() {
+        for (idx, segment) in segments.iter().enumerate() {
+            let end = segment.offset + segment.num_leds;
+            if segment.num_leds == 0 {
+                issues.push(SegmentIssue::Empty {
+                    zone_id,
+                    segment: segment.name.clone(),
+                });
+            } else if end > zone_leds {
+                issues.push(SegmentIssue::OutOfZone {
+                    zone_id,
+                    segment: segment.name.clone(),
+                    end,
+                    zone_leds,
+                });
+            }
+            for other in &segments[idx + 1..] {
+                if segment.offset < other.offset + other.num_leds && other.offset < end {
+                    issues.push(SegmentIssue::Overlap {
+                        zone_id,
+                        first: segment.name.clone(),
+                        second: other.name.clone(),
+                    });
+                }
+            }
+        }
+    }
+    issues
+}
+
+/// Clears all segments of the controller with id `controller_id` and adds `zones` in order.
+///
+/// `zones` are pairs of a zone id and the segments of that zone.
+pub(crate) async fn replace_segments<'a>(
+    proto: &OpenRgbProtocol,
+    controller_id: usize,
+    zones: impl IntoIterator<Item = (usize, &'a [SegmentState])>,
+) -> OpenRgbResult<()> {
+    let controller_id = controller_id as u32;
+    proto.clear_segments(controller_id).await?;
+    for (zone_id, segments) in zones {
+        for segment in segments {
+            proto
+                .add_segment(controller_id, zone_id as u32, &segment.to_segment_data())
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+fn segment_not_found(zone_id: usize, index: usize) -> OpenRgbError {
+    OpenRgbError::CommandError(format!("Segment {index} not found in zone {zone_id}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OpenRgbClient, ZoneType};
+
+    fn segment(name: &str, offset: usize, num_leds: usize) -> SegmentState {
+        SegmentState {
+            name: name.to_owned(),
+            offset,
+            num_leds,
+            seg_type: ZoneType::Linear,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let zones = vec![
+            vec![segment("A", 0, 5), segment("B", 5, 5)],
+            vec![segment("C", 0, 4), segment("D", 3, 2), segment("E", 8, 4)],
+            vec![segment("F", 1, 0)],
+        ];
+        assert_eq!(
+            validate(&[10, 10, 10], &zones),
+            [
+                SegmentIssue::Overlap {
+                    zone_id: 1,
+                    first: "C".to_owned(),
+                    second: "D".to_owned(),
+                },
+                SegmentIssue::OutOfZone {
+                    zone_id: 1,
+                    segment: "E".to_owned(),
+                    end: 12,
+                    zone_leds: 10,
+                },
+                SegmentIssue::Empty {
+                    zone_id: 2,
+                    segment: "F".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prune_zone_segments() {
+        let captured = vec![
+            vec![segment("A", 0, 5), segment("B", 5, 5), segment("C", 10, 5)],
+            vec![segment("D", 0, 20)],
+        ];
+
+        // probing smaller sizes must not lose segments once the original size is restored
+        let mut zones = captured.clone();
+        assert!(prune_zone_segments(&mut zones, 0, 15).is_empty());
+        assert_eq!(zones, captured);
+
+        let mut zones = captured.clone();
+        assert_eq!(
+            prune_zone_segments(&mut zones, 0, 8),
+            [segment("B", 5, 5), segment("C", 10, 5)]
+        );
+        assert_eq!(zones, [vec![segment("A", 0, 5)], vec![segment("D", 0, 20)]]);
+
+        let mut zones = captured.clone();
+        assert!(prune_zone_segments(&mut zones, 2, 0).is_empty());
+        assert_eq!(zones, captured);
+    }
+
+    #[tokio::test]
+    #[ignore = "can only test with openrgb running"]
+    async fn test_segment_manager() -> OpenRgbResult<()> {
+        let client = OpenRgbClient::connect().await?;
+        let mut controller = client.get_controller(0).await?;
+        let original = controller.segment_manager()?.zone_segments(0)?.to_vec();
+
+        let mut segments = controller.segment_manager()?;
+        segments.clear_zone(0)?;
+        segments.push(0, segment("Test", 0, 1))?;
+        segments.apply().await?;
+        assert_eq!(
+            controller.segment_manager()?.zone_segments(0)?,
+            [segment("Test", 0, 1)]
+        );
+
+        let mut segments = controller.segment_manager()?;
+        segments.clear_zone(0)?;
+        for s in original.clone() {
+            segments.push(0, s)?;
+        }
+        segments.apply().await?;
+        assert_eq!(controller.segment_manager()?.zone_segments(0)?, original);
+        Ok(())
+    }
+}
@@ -0,0 +1,594 @@
+use std::{fmt, str::FromStr};
+
+use crate::{Controller, OpenRgbError, OpenRgbResult, Segment, Zone, data::DeviceType};
+
+/// Declarative selection of controllers, zones or segments, used by [`crate::ControllerGroup::select()`].
+///
+/// Selectors can be built in code or parsed from a string, which makes it possible
+/// to target hardware in configuration files without hard-coding controller ids.
+///
+/// # Syntax
+///
+/// A selector is a list of terms of the form `field op value`, combined with
+/// `and` (or `&`, or just a space), `or` (or `|`), `not` (or `!`) and parentheses.
+/// `and` binds stronger than `or`. Values containing spaces or special characters must be quoted.
+///
+/// Fields: `type`, `vendor`, `name`, `description`, `serial`, `location`, `zone`, `segment` and `has_mode`.
+///
+/// | Operator | Meaning                              |
+/// |----------|--------------------------------------|
+/// | `:`      | equal, ignoring case                 |
+/// | `=`      | equal                                |
+/// | `~`      | contains, ignoring case              |
+/// | `^=`     | starts with                          |
+/// | `$=`     | ends with                            |
+///
+/// # Example
+///
+/// ```no_run
+/// # use openrgb2::*;
+/// # fn example(group: &ControllerGroup) -> OpenRgbResult<()> {
+/// let selector: Selector = r#"type:Keyboard or (vendor~"Corsair" and not location^="HID:")"#.parse()?;
+/// for controller in group.select(&selector) {
+///     println!("{}", controller.name());
+/// }
+///
+/// let dram: Selector = r#"name="Trident Z" zone:"DRAM""#.parse()?;
+/// for zone in group.select_zones(&dram) {
+///     println!("{} leds", zone.num_leds());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selector {
+    /// Compares a field with a value.
+    Field(SelectorField, TextMatch),
+    /// Matches if all selectors match.
+    And(Vec<Selector>),
+    /// Matches if any selector matches.
+    Or(Vec<Selector>),
+    /// Matches if the selector does not match.
+    Not(Box<Selector>),
+}
+
+/// Field compared by a [`Selector`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SelectorField {
+    /// Device type of the controller, see [`DeviceType`].
+    Type,
+    /// Vendor of the controller.
+    Vendor,
+    /// Name of the controller.
+    Name,
+    /// Description of the controller.
+    Description,
+    /// Serial of the controller.
+    Serial,
+    /// Location of the controller.
+    Location,
+    /// Name of a zone.
+    ///
+    /// When selecting controllers, matches controllers with at least one such zone.
+    Zone,
+    /// Name of a segment.
+    ///
+    /// When selecting controllers or zones, matches those with at least one such segment.
+    Segment,
+    /// Name of a mode of the controller.
+    HasMode,
+}
+
+/// Comparison of a [`SelectorField`], see [`Selector`] for the syntax.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatchOp {
+    /// Equal, ignoring case (`:`).
+    Is,
+    /// Equal (`=`).
+    Equals,
+    /// Contains, ignoring case (`~`).
+    Contains,
+    /// Starts with (`^=`).
+    StartsWith,
+    /// Ends with (`$=`).
+    EndsWith,
+}
+
+/// Operator and value to compare a field with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextMatch {
+    /// How to compare.
+    pub op: MatchOp,
+    /// Value to compare with.
+    pub value: String,
+}
+
+impl TextMatch {
+    /// Creates a new text match.
+    pub fn new(op: MatchOp, value: impl Into<String>) -> Self {
+        Self {
+            op,
+            value: value.into(),
+        }
+    }
+
+    /// Returns true if `text` matches.
+    pub fn matches(&self, text: &str) -> bool {
+        match self.op {
+            MatchOp::Is => text.eq_ignore_ascii_case(&self.value),
+            MatchOp::Equals => text == self.value,
+            MatchOp::Contains => text
+                .to_ascii_lowercase()
+                .contains(&self.value.to_ascii_lowercase()),
+            MatchOp::StartsWith => text.starts_with(&self.value),
+            MatchOp::EndsWith => text.ends_with(&self.value),
+        }
+    }
+}
+
+impl Selector {
+    /// Creates a selector comparing `field` with `value`.
+    pub fn field(field: SelectorField, op: MatchOp, value: impl Into<String>) -> Self {
+        Self::Field(field, TextMatch::new(op, value))
+    }
+
+    /// Creates a selector for controllers of type `device_type`.
+    pub fn device_type(device_type: DeviceType) -> Self {
+        Self::field(SelectorField::Type, MatchOp::Is, format!("{device_type:?}"))
+    }
+
+    /// Creates a selector for controllers that have a mode named `name`, ignoring case.
+    pub fn has_mode(name: impl Into<String>) -> Self {
+        Self::field(SelectorField::HasMode, MatchOp::Is, name)
+    }
+
+    /// Returns a selector that matches if both this and `other` match.
+    #[must_use]
+    pub fn and(self, other: Self) -> Self {
+        match self {
+            Self::And(mut all) => {
+                all.push(other);
+                Self::And(all)
+            }
+            this => Self::And(vec![this, other]),
+        }
+    }
+
+    /// Returns a selector that matches if this or `other` matches.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Self::Or(mut any) => {
+                any.push(other);
+                Self::Or(any)
+            }
+            this => Self::Or(vec![this, other]),
+        }
+    }
+
+    /// Returns true if `controller` matches this selector.
+    pub fn matches_controller(&self, controller: &Controller) -> bool {
+        self.eval(controller, None, None)
+    }
+
+    /// Returns true if `zone` matches this selector.
+    pub fn matches_zone(&self, zone: &Zone<'_>) -> bool {
+        self.eval(zone.controller(), Some(zone), None)
+    }
+
+    /// Returns true if `segment` of `zone` matches this selector.
+    pub fn matches_segment(&self, zone: &Zone<'_>, segment: &Segment<'_>) -> bool {
+        self.eval(zone.controller(), Some(zone), Some(segment.name()))
+    }
+
+    fn eval(
+        &self,
+        controller: &Controller,
+        zone: Option<&Zone<'_>>,
+        segment: Option<&str>,
+    ) -> bool {
+        match self {
+            Self::And(all) => all.iter().all(|s| s.eval(controller, zone, segment)),
+            Self::Or(any) => any.iter().any(|s| s.eval(controller, zone, segment)),
+            Self::Not(s) => !s.eval(controller, zone, segment),
+            Self::Field(field, m) => match field {
+                SelectorField::Type => m.matches(&format!("{:?}", controller.device_type())),
+                SelectorField::Vendor => m.matches(controller.vendor()),
+                SelectorField::Name => m.matches(controller.name()),
+                SelectorField::Description => m.matches(controller.description()),
+                SelectorField::Serial => m.matches(controller.serial()),
+                SelectorField::Location => m.matches(controller.location()),
+                SelectorField::HasMode => controller.mode_iter().any(|mode| m.matches(mode.name())),
+                SelectorField::Zone => match zone {
+                    Some(zone) => m.matches(zone.name()),
+                    None => controller
+                        .get_all_zones()
+                        .any(|zone| m.matches(zone.name())),
+                },
+                SelectorField::Segment => match (segment, zone) {
+                    (Some(segment), _) => m.matches(segment),
+                    (None, Some(zone)) => zone.segment_iter().any(|s| m.matches(s.name())),
+                    (None, None) => controller
+                        .get_all_zones()
+                        .any(|zone| zone.segment_iter().any(|s| m.matches(s.name()))),
+                },
+            },
+        }
+    }
+}
+
+/// Segment selected by [`crate::ControllerGroup::select_segments()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneSegment<'c> {
+    zone: Zone<'c>,
+    segment_id: usize,
+}
+
+impl<'c> ZoneSegment<'c> {
+    pub(crate) fn new(zone: Zone<'c>, segment_id: usize) -> Self {
+        Self { zone, segment_id }
+    }
+
+    /// Returns the zone of the segment.
+    pub fn zone(&self) -> &Zone<'c> {
+        &self.zone
+    }
+
+    /// Returns the segment.
+    pub fn segment(&self) -> Segment<'_> {
+        self.zone
+            .get_segment(self.segment_id)
+            .expect("segment id was taken from the zone")
+    }
+}
+
+impl std::ops::Not for Selector {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+}
+
+impl SelectorField {
+    const ALL: [Self; 9] = [
+        Self::Type,
+        Self::Vendor,
+        Self::Name,
+        Self::Description,
+        Self::Serial,
+        Self::Location,
+        Self::Zone,
+        Self::Segment,
+        Self::HasMode,
+    ];
+
+    /// Returns the name of this field in the selector syntax.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Type => "type",
+            Self::Vendor => "vendor",
+            Self::Name => "name",
+            Self::Description => "description",
+            Self::Serial => "serial",
+            Self::Location => "location",
+            Self::Zone => "zone",
+            Self::Segment => "segment",
+            Self::HasMode => "has_mode",
+        }
+    }
+}
+
+impl MatchOp {
+    /// Operators ordered so that no operator is a prefix of a later one.
+    const ALL: [Self; 5] = [
+        Self::StartsWith,
+        Self::EndsWith,
+        Self::Is,
+        Self::Equals,
+        Self::Contains,
+    ];
+
+    /// Returns the operator in the selector syntax.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Is => ":",
+            Self::Equals => "=",
+            Self::Contains => "~",
+            Self::StartsWith => "^=",
+            Self::EndsWith => "$=",
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |f: &mut fmt::Formatter<'_>, list: &[Self], sep: &str| {
+            for (idx, s) in list.iter().enumerate() {
+                if idx > 0 {
+                    f.write_str(sep)?;
+                }
+                match s {
+                    Self::And(_) | Self::Or(_) => write!(f, "({s})")?,
+                    _ => write!(f, "{s}")?,
+                }
+            }
+            Ok(())
+        };
+        match self {
+            Self::Field(field, m) => {
+                write!(f, "{}{}", field.as_str(), m.op.as_str())?;
+                if !m.value.is_empty() && m.value.chars().all(is_bare_char) {
+                    f.write_str(&m.value)
+                } else {
+                    write!(
+                        f,
+                        "\"{}\"",
+                        m.value.replace('\\', "\\\\").replace('"', "\\\"")
+                    )
+                }
+            }
+            Self::And(all) => join(f, all, " and "),
+            Self::Or(any) => join(f, any, " or "),
+            Self::Not(s) => match **s {
+                Self::And(_) | Self::Or(_) => write!(f, "not ({s})"),
+                _ => write!(f, "not {s}"),
+            },
+        }
+    }
+}
+
+impl FromStr for Selector {
+    type Err = OpenRgbError;
+
+    fn from_str(s: &str) -> OpenRgbResult<Self> {
+        let mut parser = Parser { input: s, pos: 0 };
+        let selector = parser.parse_or()?;
+        parser.skip_whitespace();
+        if parser.pos < s.len() {
+            return Err(parser.error("unexpected input"));
+        }
+        Ok(selector)
+    }
+}
+
+/// Returns true if `c` can be part of an unquoted value.
+fn is_bare_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | ':' | '#')
+}
+
+/// Recursive descent parser for the selector syntax.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn error(&self, msg: &str) -> OpenRgbError {
+        OpenRgbError::ParseError(format!(
+            "Invalid selector {:?}: {msg} at position {}",
+            self.input, self.pos
+        ))
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.pos = self.input.len() - self.rest().trim_start().len();
+    }
+
+    /// Consumes one of `symbols` or the keyword `word` if the input continues with it.
+    fn eat(&mut self, symbols: &[&str], word: &str) -> bool {
+        self.skip_whitespace();
+        let rest = self.rest();
+        if let Some(symbol) = symbols.iter().find(|s| rest.starts_with(**s)) {
+            self.pos += symbol.len();
+            return true;
+        }
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        if !word.is_empty() && rest[..len].eq_ignore_ascii_case(word) {
+            self.pos += len;
+            return true;
+        }
+        false
+    }
+
+    /// Returns true if the input continues with `or`, without consuming it.
+    fn at_or(&mut self) -> bool {
+        let pos = self.pos;
+        let found = self.eat(&["||", "|"], "or");
+        self.pos = pos;
+        found
+    }
+
+    fn parse_or(&mut self) -> OpenRgbResult<Selector> {
+        let mut any = vec![self.parse_and()?];
+        while self.eat(&["||", "|"], "or") {
+            any.push(self.parse_and()?);
+        }
+        Ok(match any.len() {
+            1 => any.remove(0),
+            _ => Selector::Or(any),
+        })
+    }
+
+    fn parse_and(&mut self) -> OpenRgbResult<Selector> {
+        let mut all = vec![self.parse_unary()?];
+        loop {
+            // terms next to each other are combined with `and`
+            let explicit = self.eat(&["&&", "&"], "and");
+            if !explicit && (self.rest().is_empty() || self.rest().starts_with(')') || self.at_or())
+            {
+                break;
+            }
+            all.push(self.parse_unary()?);
+        }
+        Ok(match all.len() {
+            1 => all.remove(0),
+            _ => Selector::And(all),
+        })
+    }
+
+    fn parse_unary(&mut self) -> OpenRgbResult<Selector> {
+        if self.eat(&["!"], "not") {
+            return Ok(!self.parse_unary()?);
+        }
+        if self.eat(&["("], "") {
+            let selector = self.parse_or()?;
+            if !self.eat(&[")"], "") {
+                return Err(self.error("expected `)`"));
+            }
+            return Ok(selector);
+        }
+        self.parse_field()
+    }
+
+    fn parse_field(&mut self) -> OpenRgbResult<Selector> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphabetic() || c == '_'))
+            .unwrap_or(rest.len());
+        let Some(field) = SelectorField::ALL
+            .into_iter()
+            .find(|f| f.as_str().eq_ignore_ascii_case(&rest[..len]))
+        else {
+            return Err(self.error("expected a field name"));
+        };
+        self.pos += len;
+
+        let Some(op) = MatchOp::ALL
+            .into_iter()
+            .find(|op| self.rest().starts_with(op.as_str()))
+        else {
+            return Err(self.error("expected one of `:`, `=`, `~`, `^=` or `$=`"));
+        };
+        self.pos += op.as_str().len();
+
+        let value = self.parse_value()?;
+        if field == SelectorField::Type && matches!(op, MatchOp::Is | MatchOp::Equals) {
+            let known = (0..)
+                .map_while(|i| DeviceType::try_from(i).ok())
+                .any(|t| TextMatch::new(op, value.as_str()).matches(&format!("{t:?}")));
+            if !known {
+                return Err(self.error(&format!("unknown device type {value:?}")));
+            }
+        }
+        Ok(Selector::field(field, op, value))
+    }
+
+    fn parse_value(&mut self) -> OpenRgbResult<String> {
+        let rest = self.rest();
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = quoted.char_indices();
+            while let Some((idx, c)) = chars.next() {
+                match c {
+                    '"' => {
+                        self.pos += idx + 2;
+                        return Ok(value);
+                    }
+                    '\\' => value.extend(chars.next().map(|(_, c)| c)),
+                    c => value.push(c),
+                }
+            }
+            return Err(self.error("unterminated string"));
+        }
+        let len = rest.find(|c| !is_bare_char(c)).unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected a value"));
+        }
+        self.pos += len;
+        Ok(rest[..len].to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(field: SelectorField, op: MatchOp, value: &str) -> Selector {
+        Selector::field(field, op, value)
+    }
+
+    #[test]
+    fn test_parse() -> OpenRgbResult<()> {
+        assert_eq!(
+            "type:Keyboard".parse::<Selector>()?,
+            Selector::device_type(DeviceType::Keyboard)
+        );
+        assert_eq!(
+            r#"name="Trident Z" zone:"DRAM""#.parse::<Selector>()?,
+            Selector::And(vec![
+                field(SelectorField::Name, MatchOp::Equals, "Trident Z"),
+                field(SelectorField::Zone, MatchOp::Is, "DRAM"),
+            ])
+        );
+        assert_eq!(
+            r#"vendor~"Corsair" or location^="HID:" and not has_mode:Breathing"#
+                .parse::<Selector>()?,
+            field(SelectorField::Vendor, MatchOp::Contains, "Corsair").or(field(
+                SelectorField::Location,
+                MatchOp::StartsWith,
+                "HID:"
+            )
+            .and(!Selector::has_mode("Breathing")))
+        );
+        assert_eq!(
+            "(type:dram | type:gpu) & serial$=01".parse::<Selector>()?,
+            field(SelectorField::Type, MatchOp::Is, "dram")
+                .or(field(SelectorField::Type, MatchOp::Is, "gpu"))
+                .and(field(SelectorField::Serial, MatchOp::EndsWith, "01"))
+        );
+        // keywords only count as whole words
+        assert_eq!(
+            "name:order".parse::<Selector>()?,
+            field(SelectorField::Name, MatchOp::Is, "order")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for s in [
+            "",
+            "type:Toaster",
+            "colour:red",
+            "name",
+            "name:",
+            "name:\"open",
+            "(type:Gpu",
+            "type:Gpu)",
+            "type:Gpu or",
+        ] {
+            assert!(s.parse::<Selector>().is_err(), "{s:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn test_display_roundtrip() -> OpenRgbResult<()> {
+        for s in [
+            "type:Keyboard",
+            r#"name="Trident Z" and zone:DRAM"#,
+            r#"(vendor~Corsair or location^="HID: \"x\"") and not (has_mode:Breathing or segment$=Left)"#,
+        ] {
+            let selector = s.parse::<Selector>()?;
+            assert_eq!(selector.to_string(), s);
+            assert_eq!(selector.to_string().parse::<Selector>()?, selector);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_text_match() {
+        assert!(TextMatch::new(MatchOp::Is, "corsair").matches("Corsair"));
+        assert!(!TextMatch::new(MatchOp::Equals, "corsair").matches("Corsair"));
+        assert!(TextMatch::new(MatchOp::Contains, "VENGEANCE").matches("Corsair Vengeance"));
+        assert!(TextMatch::new(MatchOp::StartsWith, "HID:").matches("HID: /dev/hidraw0"));
+        assert!(TextMatch::new(MatchOp::EndsWith, "0x58").matches("I2C: 0x58"));
+    }
+}
@@ -0,0 +1,667 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Color, ColorMode, Controller, ControllerGroup, ControllerMode, DeviceId, Direction, ModeData,
+    OpenRgbError, OpenRgbResult, Zone, ZoneType, client::segment_manager::replace_segments,
+    data::SegmentData,
+};
+
+/// Settings of a controller mode, see [`ControllerState`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ModeState {
+    /// Index of the mode in the controller.
+    pub id: usize,
+    /// Name of the mode.
+    pub name: String,
+    /// Speed, if the mode has a speed.
+    pub speed: Option<u32>,
+    /// Brightness, if the mode has a brightness.
+    pub brightness: Option<u32>,
+    /// Direction, if the mode has a direction.
+    pub direction: Option<Direction>,
+    /// Color mode.
+    pub color_mode: ColorMode,
+    /// Mode specific colors.
+    pub colors: Vec<Color>,
+}
+
+impl ModeState {
+    /// Captures the settings of `mode`.
+    pub fn capture(mode: &ControllerMode<'_>) -> Self {
+        Self {
+            id: mode.id(),
+            name: mode.name().to_owned(),
+            speed: mode.speed(),
+            brightness: mode.brightness(),
+            direction: mode.direction(),
+            color_mode: mode.color_mode(),
+            colors: mode.colors().to_vec(),
+        }
+    }
+
+    /// Returns the mode of `controller` with these settings applied.
+    ///
+    /// The mode is looked up by id, or by name if the mode with that id has a different name.
+    fn to_mode_data(&self, controller: &Controller) -> OpenRgbResult<ModeData> {
+        let modes = controller.modes();
+        let mode = modes
+            .get(self.id)
+            .filter(|m| m.name() == self.name)
+            .or_else(|| modes.iter().find(|m| m.name() == self.name))
+            .ok_or_else(|| {
+                OpenRgbError::CommandError(format!(
+                    "Mode {} not found in controller {}",
+                    self.name,
+                    controller.name()
+                ))
+            })?;
+        let mut data = mode.clone();
+        if let Some(speed) = self.speed {
+            data.set_speed(speed);
+        }
+        if let Some(brightness) = self.brightness {
+            data.set_brightness(brightness);
+        }
+        if let Some(direction) = self.direction {
+            data.set_direction(direction);
+        }
+        data.set_colors(self.colors.clone());
+        data.set_color_mode(self.color_mode);
+        Ok(data)
+    }
+}
+
+/// A segment of a zone, see [`ControllerState`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SegmentState {
+    /// Name of the segment.
+    pub name: String,
+    /// Index of the first LED of the segment in the zone.
+    pub offset: usize,
+    /// Number of LEDs in the segment.
+    pub num_leds: usize,
+    /// Type of the segment, usually the type of its zone.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub seg_type: ZoneType,
+}
+
+impl SegmentState {
+    /// Captures the settings of `segment`.
+    pub(crate) fn capture(segment: &SegmentData) -> Self {
+        Self {
+            name: segment.name().to_owned(),
+            offset: segment.offset() as usize,
+            num_leds: segment.led_count() as usize,
+            seg_type: segment.seg_type(),
+        }
+    }
+
+    /// Returns the segment data to send to the server.
+    pub(crate) fn to_segment_data(&self) -> SegmentData {
+        let mut data =
+            SegmentData::new(self.name.as_str(), self.offset as u32, self.num_leds as u32);
+        data.set_seg_type(self.seg_type);
+        data
+    }
+}
+
+/// Size and segments of a zone, see [`ControllerState`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZoneState {
+    /// Id of the zone.
+    pub id: usize,
+    /// Name of the zone.
+    pub name: String,
+    /// Number of LEDs in the zone.
+    pub num_leds: usize,
+    /// Segments of the zone, `None` if the protocol version does not support segments.
+    pub segments: Option<Vec<SegmentState>>,
+}
+
+/// State of a single controller, see [`StateSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ControllerState {
+    /// Id of the controller when the state was captured.
+    ///
+    /// Only informational, controllers are found by [`Self::device`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub id: usize,
+    /// Identity of the device.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub device: DeviceId,
+    /// Active mode.
+    pub mode: ModeState,
+    /// Colors of all LEDs.
+    pub colors: Vec<Color>,
+    /// Zones of the controller.
+    pub zones: Vec<ZoneState>,
+}
+
+impl ControllerState {
+    /// Captures the state of `controller`.
+    ///
+    /// This uses the controller data as last fetched from the server,
+    /// call [`Controller::sync_controller_data()`] first to capture the current state.
+    pub fn capture(controller: &Controller) -> Self {
+        Self {
+            id: controller.id(),
+            device: controller.device_id(),
+            mode: ModeState::capture(&controller.active_mode()),
+            colors: controller.colors().to_vec(),
+            zones: controller
+                .get_all_zones()
+                .map(|zone| ZoneState {
+                    id: zone.zone_id(),
+                    name: zone.name().to_owned(),
+                    num_leds: zone.num_leds(),
+                    segments: segments_of(&zone),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns true if this state could have been captured from `controller`.
+    ///
+    /// Controllers are matched by their [`DeviceId`], see [`DeviceId::match_quality()`].
+    /// The id is not used, as it can change when devices are rescanned.
+    pub fn matches(&self, controller: &Controller) -> bool {
+        self.device.matches(controller).is_some()
+    }
+
+    /// Returns what would change when restoring this state to `controller`.
+    ///
+    /// This compares against the controller data as last fetched from the server.
+    /// Missing zones come first, the other changes are in the order they are restored.
+    pub fn diff(&self, controller: &Controller) -> Vec<StateChange> {
+        self.diff_state(&Self::capture(controller))
+    }
+
+    /// Returns what would change when restoring this state to a controller in the `current` state.
+    fn diff_state(&self, current: &Self) -> Vec<StateChange> {
+        let current_zone = |state: &ZoneState| current.zones.iter().find(|z| z.id == state.id);
+        let mut changes = Vec::new();
+        let mut resized = Vec::new();
+        for state in &self.zones {
+            match current_zone(state).filter(|zone| zone.name == state.name) {
+                None => changes.push(StateChange::ZoneMissing {
+                    zone: state.name.clone(),
+                }),
+                Some(zone) if zone.num_leds != state.num_leds => {
+                    resized.push(StateChange::ZoneSize {
+                        zone_id: state.id,
+                        from: zone.num_leds,
+                        to: state.num_leds,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        changes.append(&mut resized);
+
+        let segments_changed = self.zones.iter().any(|state| {
+            state.segments.is_some()
+                && current_zone(state).is_some_and(|zone| zone.segments != state.segments)
+        });
+        if segments_changed {
+            changes.push(StateChange::Segments);
+        }
+
+        let mode_changed = ModeState {
+            id: self.mode.id,
+            ..current.mode.clone()
+        } != self.mode;
+        if mode_changed {
+            changes.push(StateChange::Mode {
+                from: current.mode.clone(),
+                to: self.mode.clone(),
+            });
+        }
+
+        let colors = &current.colors;
+        let changed_leds = match colors.len() == self.colors.len() {
+            true => colors
+                .iter()
+                .zip(&self.colors)
+                .filter(|(a, b)| a != b)
+                .count(),
+            false => self.colors.len(),
+        };
+        // changing the mode can reset the colors, so they are always sent after a mode change
+        if changed_leds > 0 || (mode_changed && !self.colors.is_empty()) {
+            changes.push(StateChange::Colors {
+                changed: changed_leds,
+            });
+        }
+        changes
+    }
+
+    /// Restores this state to `controller`, only sending the changes returned by [`Self::diff()`].
+    ///
+    /// Zones are resized first, then segments are recreated, the mode is applied
+    /// and finally the LED colors are set. The colors are sent as captured,
+    /// brightness and calibration of the controller are not applied again.
+    /// Nothing is sent if a zone is missing or the captured mode no longer exists.
+    ///
+    /// Afterwards the controller data is synced with the server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a zone of this state does not exist on `controller`,
+    /// the captured mode no longer exists, or communication with the server fails.
+    pub async fn restore(&self, controller: &mut Controller) -> OpenRgbResult<()> {
+        let changes = self.diff(controller);
+        if changes.is_empty() {
+            return Ok(());
+        }
+        if let Some(StateChange::ZoneMissing { zone }) = changes
+            .iter()
+            .find(|c| matches!(c, StateChange::ZoneMissing { .. }))
+        {
+            return Err(OpenRgbError::CommandError(format!(
+                "Zone {zone} not found in controller {}",
+                controller.name()
+            )));
+        }
+        let mode = match changes
+            .iter()
+            .any(|c| matches!(c, StateChange::Mode { .. }))
+        {
+            true => Some(self.mode.to_mode_data(controller)?),
+            false => None,
+        };
+        let id = controller.id() as u32;
+        let proto = controller.proto();
+
+        for change in &changes {
+            match change {
+                StateChange::ZoneMissing { .. } => unreachable!("missing zones are checked above"),
+                StateChange::ZoneSize { zone_id, to, .. } => {
+                    controller.get_zone(*zone_id)?.resize(*to).await?;
+                }
+                StateChange::Segments => {
+                    let zones = self
+                        .zones
+                        .iter()
+                        .map(|zone| (zone.id, zone.segments.as_deref().unwrap_or_default()));
+                    replace_segments(proto, controller.id(), zones).await?;
+                }
+                StateChange::Mode { .. } => {
+                    if let Some(mode) = &mode {
+                        proto.update_mode(id, mode).await?;
+                    }
+                }
+                StateChange::Colors { .. } => {
+                    proto.update_leds(id, &self.colors).await?;
+                }
+            }
+        }
+        controller.sync_controller_data().await
+    }
+}
+
+/// A difference between a [`ControllerState`] and a controller, see [`ControllerState::diff()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateChange {
+    /// The zone does not exist on the controller, the state can not be restored.
+    ZoneMissing {
+        /// Name of the zone.
+        zone: String,
+    },
+    /// The zone will be resized.
+    ZoneSize {
+        /// Id of the zone.
+        zone_id: usize,
+        /// Current number of LEDs.
+        from: usize,
+        /// Number of LEDs after restoring.
+        to: usize,
+    },
+    /// The segments will be cleared and recreated.
+    Segments,
+    /// The mode or its settings will change.
+    Mode {
+        /// Current mode.
+        from: ModeState,
+        /// Mode after restoring.
+        to: ModeState,
+    },
+    /// The LED colors will be updated.
+    Colors {
+        /// Number of LEDs with a different color.
+        changed: usize,
+    },
+}
+
+impl std::fmt::Display for StateChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ZoneMissing { zone } => write!(f, "zone {zone} is missing"),
+            Self::ZoneSize { zone_id, from, to } => {
+                write!(f, "resize zone {zone_id} from {from} to {to} LEDs")
+            }
+            Self::Segments => write!(f, "recreate segments"),
+            Self::Mode { from, to } if from.name == to.name => {
+                write!(f, "change settings of mode {}", to.name)
+            }
+            Self::Mode { from, to } => write!(f, "change mode from {} to {}", from.name, to.name),
+            Self::Colors { changed } => write!(f, "set colors ({changed} LEDs changed)"),
+        }
+    }
+}
+
+/// Returns the segments of `zone`, `None` if the protocol version does not support segments.
+fn segments_of(zone: &Zone<'_>) -> Option<Vec<SegmentState>> {
+    Some(
+        zone.segment_data()?
+            .iter()
+            .map(SegmentState::capture)
+            .collect(),
+    )
+}
+
+/// Snapshot of the state of multiple controllers.
+///
+/// Use this to capture the lights before taking control of them, and restore them afterwards.
+/// With the `serde` feature the snapshot can be stored, e.g. to restore the lights after a crash.
+///
+/// # Example
+///
+/// ```no_run
+/// # use openrgb2::*;
+/// # async fn example() -> OpenRgbResult<()> {
+/// let client = OpenRgbClient::connect().await?;
+/// let mut controllers = client.get_all_controllers().await?;
+/// let snapshot = StateSnapshot::capture(&controllers);
+///
+/// controllers.init().await?;
+/// // ...
+///
+/// snapshot.restore(&mut controllers).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct StateSnapshot {
+    /// State per controller.
+    pub controllers: Vec<ControllerState>,
+}
+
+impl StateSnapshot {
+    /// Captures the state of all controllers in `group`.
+    ///
+    /// See [`ControllerState::capture()`].
+    pub fn capture(group: &ControllerGroup) -> Self {
+        Self {
+            controllers: group.iter().map(ControllerState::capture).collect(),
+        }
+    }
+
+    /// Returns the captured state of `controller`.
+    ///
+    /// Prefers the state with the best [`crate::DeviceMatch`], so identical devices are not mixed up.
+    pub fn get(&self, controller: &Controller) -> Option<&ControllerState> {
+        self.controllers
+            .iter()
+            .filter_map(|s| Some((s.device.matches(controller)?, s)))
+            .min_by_key(|(quality, _)| *quality)
+            .map(|(_, s)| s)
+    }
+
+    /// Restores the captured state to the controllers in `group`.
+    ///
+    /// Captured controllers are found in `group` with [`ControllerGroup::resolve()`].
+    /// Controllers that are not in this snapshot are left unchanged,
+    /// captured controllers that are not in `group` are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error that occurred, see [`ControllerState::restore()`].
+    /// The remaining controllers are still restored.
+    pub async fn restore(&self, group: &mut ControllerGroup) -> OpenRgbResult<()> {
+        restore_states(&self.controllers, group).await
+    }
+
+    /// Serializes this snapshot to a TOML string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot cannot be represented as TOML.
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> OpenRgbResult<String> {
+        #[derive(Serialize)]
+        struct Wrapper<'a> {
+            controllers: &'a [ControllerState],
+        }
+        super::toml_file::to_string(&Wrapper {
+            controllers: &self.controllers,
+        })
+    }
+
+    /// Parses a snapshot from a TOML string created by [`Self::to_toml()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a valid snapshot.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> OpenRgbResult<Self> {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            controllers: Vec<ControllerState>,
+        }
+        let wrapper: Wrapper = super::toml_file::from_str(s)?;
+        Ok(Self {
+            controllers: wrapper.controllers,
+        })
+    }
+
+    /// Writes this snapshot to a TOML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    #[cfg(feature = "toml")]
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> OpenRgbResult<()> {
+        super::toml_file::write(path.as_ref(), &self.to_toml()?)
+    }
+
+    /// Reads a snapshot from a TOML file written by [`Self::save()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid snapshot.
+    #[cfg(feature = "toml")]
+    pub fn load(path: impl AsRef<std::path::Path>) -> OpenRgbResult<Self> {
+        Self::from_toml(&super::toml_file::read(path.as_ref())?)
+    }
+}
+
+/// Restores each of `states` to its controller in `group`.
+///
+/// Shared by [`StateSnapshot::restore()`] and [`crate::Profile::apply()`].
+pub(crate) async fn restore_states(
+    states: &[ControllerState],
+    group: &mut ControllerGroup,
+) -> OpenRgbResult<()> {
+    let positions = group.resolve_positions(states.iter().map(|s| &s.device));
+    let mut result = Ok(());
+    for (state, pos) in states.iter().zip(positions) {
+        let Some(pos) = pos else {
+            continue;
+        };
+        let controller = &mut group.controllers_mut()[pos];
+        if let Err(e) = state.restore(controller).await {
+            tracing::warn!("Failed to restore {}: {e}", controller.name());
+            if result.is_ok() {
+                result = Err(e);
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::OpenRgbClient;
+
+    use super::*;
+
+    fn state() -> ControllerState {
+        ControllerState {
+            id: 2,
+            device: DeviceId {
+                vendor: "Vendor".to_owned(),
+                name: "Strip".to_owned(),
+                serial: String::new(),
+                location: "HID: /dev/hidraw0".to_owned(),
+                device_type: crate::DeviceType::LEDStrip,
+            },
+            mode: ModeState {
+                id: 1,
+                name: "Breathing".to_owned(),
+                speed: Some(100),
+                brightness: None,
+                direction: Some(Direction::Left),
+                color_mode: ColorMode::ModeSpecific,
+                colors: vec![Color::new(255, 0, 0)],
+            },
+            colors: vec![Color::new(1, 2, 3); 3],
+            zones: vec![ZoneState {
+                id: 0,
+                name: "Zone".to_owned(),
+                num_leds: 3,
+                segments: Some(vec![SegmentState {
+                    name: "Left".to_owned(),
+                    offset: 0,
+                    num_leds: 2,
+                    seg_type: ZoneType::Linear,
+                }]),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let captured = state();
+        assert!(captured.diff_state(&captured).is_empty());
+
+        let mut current = captured.clone();
+        current.colors[1] = Color::new(0, 0, 0);
+        assert_eq!(
+            captured.diff_state(&current),
+            [StateChange::Colors { changed: 1 }]
+        );
+
+        // a missing zone comes before the resize of an earlier zone
+        let mut captured = state();
+        captured.zones.push(ZoneState {
+            id: 1,
+            name: "Fans".to_owned(),
+            num_leds: 8,
+            segments: None,
+        });
+        let mut current = state();
+        current.zones[0].num_leds = 5;
+        current.zones[0].segments = Some(Vec::new());
+        current.mode.name = "Static".to_owned();
+        let changes = captured.diff_state(&current);
+        assert_eq!(
+            changes,
+            [
+                StateChange::ZoneMissing {
+                    zone: "Fans".to_owned()
+                },
+                StateChange::ZoneSize {
+                    zone_id: 0,
+                    from: 5,
+                    to: 3
+                },
+                StateChange::Segments,
+                StateChange::Mode {
+                    from: current.mode.clone(),
+                    to: captured.mode.clone(),
+                },
+                StateChange::Colors { changed: 0 },
+            ]
+        );
+        assert_eq!(
+            changes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            [
+                "zone Fans is missing",
+                "resize zone 0 from 5 to 3 LEDs",
+                "recreate segments",
+                "change mode from Static to Breathing",
+                "set colors (0 LEDs changed)",
+            ]
+        );
+
+        current.mode.name = "Breathing".to_owned();
+        current.mode.speed = Some(0);
+        let change = StateChange::Mode {
+            from: current.mode,
+            to: captured.mode,
+        };
+        assert_eq!(change.to_string(), "change settings of mode Breathing");
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_roundtrip() -> OpenRgbResult<()> {
+        let snapshot = StateSnapshot {
+            controllers: vec![state()],
+        };
+        let toml = snapshot.to_toml()?;
+        assert_eq!(StateSnapshot::from_toml(&toml)?, snapshot);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "can only test with openrgb running"]
+    async fn test_snapshot_restore() -> OpenRgbResult<()> {
+        let client = OpenRgbClient::connect().await?;
+        let mut controllers = client.get_all_controllers().await?;
+        let snapshot = StateSnapshot::capture(&controllers);
+        controllers.init().await?;
+        snapshot.restore(&mut controllers).await?;
+        assert_eq!(StateSnapshot::capture(&controllers), snapshot);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "can only test with openrgb running"]
+    async fn test_restore_then_write_segment() -> OpenRgbResult<()> {
+        let client = OpenRgbClient::connect().await?;
+        let mut controller = client.get_controller(0).await?;
+        controller.set_controllable_mode().await?;
+        controller.set_all_leds(Color::new(255, 0, 0)).await?;
+        controller.sync_controller_data().await?;
+
+        let mut state = ControllerState::capture(&controller);
+        let blue = Color::new(0, 0, 255);
+        state.colors.fill(blue);
+        state.restore(&mut controller).await?;
+
+        let zone = controller.get_zone(0)?;
+        let Some(segment) = zone.segment_iter().next() else {
+            return Ok(());
+        };
+        let range = segment.offset()..segment.offset() + segment.num_leds();
+        segment.set_all_leds(Color::new(0, 255, 0)).await?;
+        controller.sync_controller_data().await?;
+
+        // the other LEDs of the zone keep the restored colors, not the colors written before
+        let colors = controller.get_zone(0)?.known_colors();
+        for (idx, color) in colors.iter().enumerate() {
+            if !range.contains(&idx) {
+                assert_eq!(*color, blue, "LED {idx} changed");
+            }
+        }
+        Ok(())
+    }
+}
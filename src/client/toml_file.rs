@@ -0,0 +1,33 @@
+//! TOML and file handling shared by the types with `from_toml()` and `load()` methods.
+
+use std::path::Path;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{OpenRgbError, OpenRgbResult};
+
+/// Parses `s` as TOML.
+pub(crate) fn from_str<T: DeserializeOwned>(s: &str) -> OpenRgbResult<T> {
+    toml::from_str(s).map_err(|e| OpenRgbError::ParseError(e.to_string()))
+}
+
+/// Serializes `value` to a TOML string.
+pub(crate) fn to_string<T: Serialize>(value: &T) -> OpenRgbResult<String> {
+    toml::to_string(value).map_err(|e| OpenRgbError::ParseError(e.to_string()))
+}
+
+/// Reads the file at `path` to a string.
+pub(crate) fn read(path: &Path) -> OpenRgbResult<String> {
+    std::fs::read_to_string(path).map_err(|source| OpenRgbError::FileError {
+        path: path.display().to_string(),
+        source,
+    })
+}
+
+/// Writes `contents` to the file at `path`.
+pub(crate) fn write(path: &Path, contents: &str) -> OpenRgbResult<()> {
+    std::fs::write(path, contents).map_err(|source| OpenRgbError::FileError {
+        path: path.display().to_string(),
+        source,
+    })
+}
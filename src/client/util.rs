@@ -0,0 +1,52 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Polls all `futures` concurrently on the current task, returning their outputs in order.
+pub(crate) async fn join_all<F: Future>(futures: Vec<F>) -> Vec<F::Output> {
+    let mut futures = futures
+        .into_iter()
+        .map(|f| Some(Box::pin(f)))
+        .collect::<Vec<_>>();
+    let mut outputs = futures.iter().map(|_| None).collect::<Vec<_>>();
+    std::future::poll_fn(|cx: &mut Context<'_>| {
+        let mut pending = false;
+        for (future, output) in futures.iter_mut().zip(&mut outputs) {
+            let Some(f) = future else {
+                continue;
+            };
+            match Pin::as_mut(f).poll(cx) {
+                Poll::Ready(value) => {
+                    *output = Some(value);
+                    *future = None;
+                }
+                Poll::Pending => pending = true,
+            }
+        }
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    })
+    .await;
+    outputs.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_join_all() {
+        let futures = (0..3u64)
+            .map(|i| async move {
+                tokio::time::sleep(std::time::Duration::from_millis(30 - i * 10)).await;
+                i
+            })
+            .collect();
+        assert_eq!(join_all(futures).await, [0, 1, 2]);
+    }
+}
@@ -0,0 +1,261 @@
+use std::ops::Range;
+
+use crate::{Color, CommandGroup, ControllerGroup, OpenRgbError, OpenRgbResult, Segment, Zone};
+
+/// A range of LEDs of a single controller that is part of a [`VirtualStrip`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StripRange {
+    /// Id of the controller.
+    pub controller_id: usize,
+    /// Index of the first LED of the range in the controller.
+    pub offset: usize,
+    /// Number of LEDs in the range.
+    pub len: usize,
+    /// If true, the first LED of the range is the last LED of this part of the strip.
+    pub reversed: bool,
+}
+
+impl StripRange {
+    /// Returns the controller LED id of the LED at `idx` in this range.
+    fn led_id(&self, idx: usize) -> usize {
+        match self.reversed {
+            true => self.offset + self.len - 1 - idx,
+            false => self.offset + idx,
+        }
+    }
+}
+
+/// One logical linear LED strip made of zones, segments or LED ranges of multiple controllers.
+///
+/// LED `0` of the strip is the first LED of the first range,
+/// followed by the LEDs of the next range and so on.
+/// Ranges can be reversed, for hardware that is mounted the other way around.
+///
+/// The ranges store the LED offsets of the controllers when they were added,
+/// so the strip has to be rebuilt after zones are resized or controllers are rescanned.
+///
+/// # Example
+///
+/// ```no_run
+/// # use openrgb2::*;
+/// # async fn example(group: &ControllerGroup) -> OpenRgbResult<()> {
+/// let fans = group.get_controller(0)?;
+/// let gpu = group.get_controller(1)?;
+/// let mut strip = VirtualStrip::new();
+/// strip.add_zone(&fans.get_zone(0)?, false);
+/// // the GPU strip runs right to left
+/// strip.add_zone(&gpu.get_zone(0)?, true);
+///
+/// let colors = (0..strip.len()).map(|i| Color::new((i * 10) as u8, 0, 255));
+/// strip.cmd_with_set_leds(group, colors)?.execute().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VirtualStrip {
+    ranges: Vec<StripRange>,
+}
+
+impl VirtualStrip {
+    /// Creates an empty strip.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends all LEDs of `zone` to the end of this strip.
+    pub fn add_zone(&mut self, zone: &Zone<'_>, reversed: bool) {
+        self.push(
+            zone.controller_id(),
+            zone.offset(),
+            zone.num_leds(),
+            reversed,
+        );
+    }
+
+    /// Appends all LEDs of `segment` to the end of this strip.
+    pub fn add_segment(&mut self, zone: &Zone<'_>, segment: &Segment<'_>, reversed: bool) {
+        self.push(
+            zone.controller_id(),
+            zone.offset() + segment.offset(),
+            segment.num_leds(),
+            reversed,
+        );
+    }
+
+    /// Appends the LEDs in `range` of `zone` to the end of this strip.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `range` is out of bounds for `zone`.
+    pub fn add_zone_range(
+        &mut self,
+        zone: &Zone<'_>,
+        range: Range<usize>,
+        reversed: bool,
+    ) -> OpenRgbResult<()> {
+        if range.start > range.end || range.end > zone.num_leds() {
+            return Err(OpenRgbError::CommandError(format!(
+                "Range {range:?} is out of bounds for zone {} with {} LEDs",
+                zone.name(),
+                zone.num_leds()
+            )));
+        }
+        self.push(
+            zone.controller_id(),
+            zone.offset() + range.start,
+            range.len(),
+            reversed,
+        );
+        Ok(())
+    }
+
+    fn push(&mut self, controller_id: usize, offset: usize, len: usize, reversed: bool) {
+        if len > 0 {
+            self.ranges.push(StripRange {
+                controller_id,
+                offset,
+                len,
+                reversed,
+            });
+        }
+    }
+
+    /// Returns the ranges of this strip, in order.
+    pub fn ranges(&self) -> &[StripRange] {
+        &self.ranges
+    }
+
+    /// Returns the number of LEDs in this strip.
+    pub fn len(&self) -> usize {
+        self.ranges.iter().map(|r| r.len).sum()
+    }
+
+    /// Returns true if this strip has no LEDs.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the controller id and controller LED id of the LED at `idx` in this strip.
+    pub fn led_at(&self, mut idx: usize) -> Option<(usize, usize)> {
+        for range in &self.ranges {
+            if idx < range.len {
+                return Some((range.controller_id, range.led_id(idx)));
+            }
+            idx -= range.len;
+        }
+        None
+    }
+
+    /// Returns the controller id and controller LED id of every LED in this strip, in order.
+    pub fn led_iter(&self) -> impl Iterator<Item = (usize, usize)> {
+        self.ranges
+            .iter()
+            .flat_map(|range| (0..range.len).map(|idx| (range.controller_id, range.led_id(idx))))
+    }
+
+    /// Adds a command to `cmd` to update the LED at `idx` in this strip.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `idx` is out of bounds, or the controller is not in the group of `cmd`.
+    pub fn set_led<C: Into<Color>>(
+        &self,
+        cmd: &mut CommandGroup<'_>,
+        idx: usize,
+        color: C,
+    ) -> OpenRgbResult<()> {
+        let (controller_id, led_id) = self.led_at(idx).ok_or_else(|| {
+            OpenRgbError::CommandError(format!(
+                "LED {idx} is out of bounds for virtual strip with {} LEDs",
+                self.len()
+            ))
+        })?;
+        cmd.set_controller_led(controller_id, led_id, color)
+    }
+
+    /// Adds commands to `cmd` to update the LEDs of this strip, starting at the first LED.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `colors` contains more colors than this strip has LEDs,
+    /// or a controller of this strip is not in the group of `cmd`.
+    pub fn set_leds<C: Into<Color>>(
+        &self,
+        cmd: &mut CommandGroup<'_>,
+        colors: impl IntoIterator<Item = C>,
+    ) -> OpenRgbResult<()> {
+        let mut leds = self.led_iter();
+        for (idx, color) in colors.into_iter().enumerate() {
+            let Some((controller_id, led_id)) = leds.next() else {
+                return Err(OpenRgbError::CommandError(format!(
+                    "Virtual strip was given more than {idx} colors, while its length is {}",
+                    self.len()
+                )));
+            };
+            cmd.set_controller_led(controller_id, led_id, color)?;
+        }
+        Ok(())
+    }
+
+    /// Creates a command for `group` that updates the LEDs of this strip.
+    ///
+    /// See [`Self::set_leds()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `colors` contains more colors than this strip has LEDs,
+    /// or a controller of this strip is not in `group`.
+    pub fn cmd_with_set_leds<'a, C: Into<Color>>(
+        &self,
+        group: &'a ControllerGroup,
+        colors: impl IntoIterator<Item = C>,
+    ) -> OpenRgbResult<CommandGroup<'a>> {
+        let mut cmd = group.cmd();
+        self.set_leds(&mut cmd, colors)?;
+        Ok(cmd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OpenRgbClient;
+
+    #[test]
+    fn test_led_mapping() {
+        let mut strip = VirtualStrip::new();
+        strip.push(0, 2, 3, false);
+        strip.push(1, 10, 2, true);
+        strip.push(2, 0, 0, false);
+        assert_eq!(strip.ranges().len(), 2);
+        assert_eq!(strip.len(), 5);
+        assert_eq!(
+            strip.led_iter().collect::<Vec<_>>(),
+            [(0, 2), (0, 3), (0, 4), (1, 11), (1, 10)]
+        );
+        assert_eq!(strip.led_at(3), Some((1, 11)));
+        assert_eq!(strip.led_at(5), None);
+    }
+
+    #[tokio::test]
+    #[ignore = "can only test with openrgb running"]
+    async fn test_virtual_strip() -> OpenRgbResult<()> {
+        let client = OpenRgbClient::connect().await?;
+        let group = client.get_all_controllers().await?;
+        let mut strip = VirtualStrip::new();
+        for controller in &group {
+            for zone in controller.get_all_zones() {
+                strip.add_zone(&zone, zone.zone_id() % 2 == 1);
+            }
+        }
+        assert_eq!(strip.len(), group.iter().map(|c| c.num_leds()).sum());
+        let colors = (0..strip.len()).map(|i| Color::new((i % 256) as u8, 0, 0));
+        strip.cmd_with_set_leds(&group, colors)?.execute().await?;
+        assert!(
+            strip
+                .set_led(&mut group.cmd(), strip.len(), [0, 0, 0])
+                .is_err()
+        );
+        Ok(())
+    }
+}
@@ -1,8 +1,10 @@
 use array2d::Array2D;
+use flagset::FlagSet;
 
 use crate::{
-    Color, Command, Controller, Led, OpenRgbError, OpenRgbResult, ZoneType,
-    client::segment::Segment,
+    Color, Command, Controller, Led, OpenRgbError, OpenRgbResult, SegmentState, ZoneFlags,
+    ZoneType,
+    client::{matrix::ZoneMatrix, segment::Segment},
     data::{SegmentData, ZoneData},
 };
 
@@ -33,6 +35,10 @@ impl<'c> Zone<'c> {
         self.zone_data.id()
     }
 
+    pub(crate) fn controller(&self) -> &'c Controller {
+        self.controller
+    }
+
     delegate::delegate! {
         to self.zone_data {
             /// Returns the ID of this zone.
@@ -54,14 +60,46 @@ impl<'c> Zone<'c> {
             #[call(leds_count)]
             pub fn num_leds(&self) -> usize;
 
+            /// Returns the flags of this zone.
+            ///
+            /// Returns `None` if the protocol version is lower than 5.
+            pub fn flags(&self) -> Option<FlagSet<ZoneFlags>>;
+
             #[call(segments)]
             pub(crate) fn segment_data(&self) -> Option<&[SegmentData]>;
-            #[expect(unused, reason = "Api not finalised yet")]
             #[call(matrix)]
             pub(crate) fn matrix_data(&self) -> Option<&Array2D<u32>>;
         }
     }
 
+    /// Returns a grid view of this zone if it is a [`ZoneType::Matrix`] zone with a matrix map.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use openrgb2::{Color, OpenRgbClient, OpenRgbResult};
+    /// # async fn example() -> OpenRgbResult<()> {
+    /// # let client = OpenRgbClient::connect().await?;
+    /// # let keyboard = client.get_controller(0).await?;
+    /// let zone = keyboard.get_zone(0)?;
+    /// let matrix = zone.matrix().expect("zone is a matrix");
+    /// // light up the top row
+    /// let mut cmd = keyboard.cmd();
+    /// for col in 0..matrix.width() {
+    ///     cmd.set_matrix_pixel(zone.zone_id(), 0, col, Color::new(255, 0, 0))?;
+    /// }
+    /// cmd.execute().await
+    /// # }
+    /// ```
+    pub fn matrix(&self) -> Option<ZoneMatrix<'c>> {
+        if self.zone_type() != ZoneType::Matrix {
+            return None;
+        }
+        self.zone_data
+            .matrix()
+            .map(|map| ZoneMatrix::new(self.clone(), map))
+    }
+
     /// Returns the segment with the given `segment_id`.
     pub fn get_segment(&'c self, segment_id: usize) -> OpenRgbResult<Segment<'c>> {
         let Some(segments) = self.segment_data() else {
@@ -129,6 +167,18 @@ impl<'c> Zone<'c> {
         Ok(cmd)
     }
 
+    /// Returns the color of every LED in this zone as far as the client knows.
+    ///
+    /// See [`Controller::known_colors()`].
+    pub fn known_colors(&self) -> Vec<Color> {
+        let mut colors = self.controller.known_colors();
+        let start = self.offset().min(colors.len());
+        let end = (start + self.num_leds()).min(colors.len());
+        colors.truncate(end);
+        colors.drain(..start);
+        colors
+    }
+
     /// Sets a single LED in this zone to the given `color`.
     ///
     /// # Errors
@@ -215,10 +265,58 @@ impl<'c> Zone<'c> {
             .map(move |s| Segment::new(self, s))
     }
 
+    /// Returns true if this zone can only be resized for effects.
+    ///
+    /// Such zones represent a single LED to `OpenRGB` and cannot be resized through the SDK.
+    pub fn is_resizable_for_effects_only(&self) -> bool {
+        self.flags()
+            .is_some_and(|f| f.contains(ZoneFlags::ResizableForEffectsOnly))
+    }
+
+    /// Returns true if this zone can be resized through the SDK.
+    pub fn is_resizable(&self) -> bool {
+        self.leds_min() != self.leds_max() && !self.is_resizable_for_effects_only()
+    }
+
+    /// Returns an error if this zone cannot be resized to `new_size` LEDs.
+    pub(crate) fn check_resize(&self, new_size: usize) -> OpenRgbResult<()> {
+        if self.is_resizable_for_effects_only() {
+            return Err(OpenRgbError::CommandError(format!(
+                "Zone {} of {} is only resizable for effects",
+                self.name(),
+                self.controller.name()
+            )));
+        }
+        if !self.is_resizable() {
+            return Err(OpenRgbError::CommandError(format!(
+                "Zone {} of {} is not resizable",
+                self.name(),
+                self.controller.name()
+            )));
+        }
+        if !(self.leds_min()..=self.leds_max()).contains(&new_size) {
+            return Err(OpenRgbError::CommandError(format!(
+                "Zone {} of {} can not be resized to {new_size} LEDs, the size must be between {} and {}",
+                self.name(),
+                self.controller.name(),
+                self.leds_min(),
+                self.leds_max()
+            )));
+        }
+        Ok(())
+    }
+
     /// Resizes this zone to a new size.
     ///
-    /// Controller data must be resynced using [`Controller::sync_controller_data()`]
+    /// Controller data must be resynced using [`Controller::sync_controller_data()`],
+    /// [`Controller::resize_zone()`] does this automatically and verifies the new size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this zone is not resizable or `new_size` is outside of
+    /// [`Self::leds_min()`]`..=`[`Self::leds_max()`].
     pub async fn resize(&self, new_size: usize) -> OpenRgbResult<()> {
+        self.check_resize(new_size)?;
         self.controller
             .proto()
             .resize_zone(
@@ -229,3 +327,16 @@ impl<'c> Zone<'c> {
             .await
     }
 }
+
+/// Result of [`Controller::resize_zone()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneResize {
+    /// Id of the zone.
+    pub zone_id: usize,
+    /// Number of LEDs before resizing.
+    pub from: usize,
+    /// Number of LEDs after resizing.
+    pub to: usize,
+    /// Segments that were removed because they no longer fit in the zone.
+    pub removed_segments: Vec<SegmentState>,
+}
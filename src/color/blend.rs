@@ -0,0 +1,60 @@
+use crate::Color;
+
+/// Ways to combine two colors, see [`super::ColorExt::blend()`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlendMode {
+    /// Draws the top color over the base color with the given opacity (`0.0..=1.0`).
+    Alpha(f32),
+    /// Adds the channels together, saturating at 255.
+    Add,
+    /// Multiplies the channels, which always results in a darker color.
+    Multiply,
+    /// Inverse of multiplying the inverted channels, which always results in a lighter color.
+    Screen,
+}
+
+impl BlendMode {
+    /// Blends `top` on top of `base`.
+    pub fn blend(self, base: Color, top: Color) -> Color {
+        let channel = |b: u8, t: u8| -> u8 {
+            let (bf, tf) = (u32::from(b), u32::from(t));
+            match self {
+                Self::Alpha(alpha) => {
+                    let a = alpha.clamp(0.0, 1.0);
+                    (f32::from(b) + (f32::from(t) - f32::from(b)) * a).round() as u8
+                }
+                Self::Add => b.saturating_add(t),
+                Self::Multiply => ((bf * tf + 127) / 255) as u8,
+                Self::Screen => (255 - ((255 - bf) * (255 - tf) + 127) / 255) as u8,
+            }
+        };
+        Color::new(
+            channel(base.r, top.r),
+            channel(base.g, top.g),
+            channel(base.b, top.b),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blend() {
+        let base = Color::new(200, 100, 0);
+        let top = Color::new(100, 255, 128);
+        assert_eq!(BlendMode::Add.blend(base, top), Color::new(255, 255, 128));
+        assert_eq!(BlendMode::Multiply.blend(base, top), Color::new(78, 100, 0));
+        assert_eq!(
+            BlendMode::Screen.blend(base, top),
+            Color::new(222, 255, 128)
+        );
+        assert_eq!(BlendMode::Alpha(0.0).blend(base, top), base);
+        assert_eq!(BlendMode::Alpha(1.0).blend(base, top), top);
+        assert_eq!(
+            BlendMode::Alpha(0.5).blend(base, top),
+            Color::new(150, 178, 64)
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use crate::Color;
+
+/// Lookup table for gamma correction.
+///
+/// LEDs respond linearly to their PWM duty cycle, while our eyes do not.
+/// Applying a gamma curve makes fades look perceptually even.
+/// A gamma of 2.2 to 2.8 is typical for addressable LEDs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GammaTable {
+    table: [u8; 256],
+}
+
+impl GammaTable {
+    /// Creates a lookup table for the curve `out = in ^ gamma`.
+    pub fn new(gamma: f32) -> Self {
+        let mut table = [0; 256];
+        for (i, v) in table.iter_mut().enumerate() {
+            *v = ((i as f32 / 255.0).powf(gamma) * 255.0).round() as u8;
+        }
+        Self { table }
+    }
+
+    /// Creates a lookup table that leaves colors unchanged.
+    pub fn identity() -> Self {
+        Self::new(1.0)
+    }
+
+    /// Corrects a single channel value.
+    pub fn correct(&self, value: u8) -> u8 {
+        self.table[value as usize]
+    }
+
+    /// Corrects all channels of `color`.
+    pub fn apply(&self, color: Color) -> Color {
+        Color::new(
+            self.correct(color.r),
+            self.correct(color.g),
+            self.correct(color.b),
+        )
+    }
+}
+
+impl Default for GammaTable {
+    /// Gamma of 2.2
+    fn default() -> Self {
+        Self::new(2.2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gamma() {
+        let identity = GammaTable::identity();
+        assert!((0..=255).all(|v| identity.correct(v) == v));
+
+        let table = GammaTable::new(2.0);
+        assert_eq!(table.correct(0), 0);
+        assert_eq!(table.correct(255), 255);
+        assert_eq!(table.correct(128), 64);
+        assert_eq!(table.apply(Color::new(255, 128, 0)), Color::new(255, 64, 0));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::Color;
+
+use super::{ColorExt, OkLab};
+
+/// Color space in which a [`Gradient`] interpolates between stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// Interpolate the RGB values directly.
+    #[default]
+    Rgb,
+    /// Interpolate in the perceptual `OKLab` space, which avoids muddy colors halfway.
+    OkLab,
+}
+
+/// Gradient with multiple color stops.
+///
+/// # Example
+///
+/// ```
+/// use openrgb2::{Color, color::Gradient};
+///
+/// let gradient = Gradient::new([
+///     (0.0, Color::new(255, 0, 0)),
+///     (0.8, Color::new(255, 255, 0)),
+///     (1.0, Color::new(255, 255, 255)),
+/// ]);
+/// assert_eq!(gradient.sample(0.0), Color::new(255, 0, 0));
+/// // one color for every LED of a 30 LED strip
+/// let colors = gradient.sample_n(30);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f32, Color)>,
+    interpolation: Interpolation,
+}
+
+impl Gradient {
+    /// Creates a gradient from `(position, color)` stops, positions range from 0.0 to 1.0.
+    ///
+    /// Stops do not have to be sorted.
+    pub fn new<C: Into<Color>>(stops: impl IntoIterator<Item = (f32, C)>) -> Self {
+        let mut stops = stops
+            .into_iter()
+            .map(|(p, c)| (p.clamp(0.0, 1.0), c.into()))
+            .collect::<Vec<_>>();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self {
+            stops,
+            interpolation: Interpolation::default(),
+        }
+    }
+
+    /// Creates a gradient with evenly spaced `colors`.
+    pub fn even<C: Into<Color>>(colors: impl IntoIterator<Item = C>) -> Self {
+        let colors = colors.into_iter().map(Into::into).collect::<Vec<Color>>();
+        let n = colors.len();
+        Self::new(colors.into_iter().enumerate().map(|(i, c)| {
+            let p = match n {
+                0 | 1 => 0.0,
+                _ => i as f32 / (n - 1) as f32,
+            };
+            (p, c)
+        }))
+    }
+
+    /// Sets the color space used to interpolate between stops.
+    #[must_use]
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Returns the stops of this gradient, sorted by position.
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+
+    /// Returns the color at position `t`, which is clamped to `0.0..=1.0`.
+    ///
+    /// An empty gradient is black.
+    pub fn sample(&self, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else {
+            return Color::default();
+        };
+        if t <= first.0 {
+            return first.1;
+        }
+        if t >= last.0 {
+            return last.1;
+        }
+
+        let idx = self.stops.partition_point(|(p, _)| *p <= t);
+        let (p0, c0) = self.stops[idx - 1];
+        let (p1, c1) = self.stops[idx];
+        let local = match p1 - p0 > 0.0 {
+            true => (t - p0) / (p1 - p0),
+            false => 1.0,
+        };
+        match self.interpolation {
+            Interpolation::Rgb => c0.lerp(c1, local),
+            Interpolation::OkLab => OkLab::from(c0).lerp(OkLab::from(c1), local).into(),
+        }
+    }
+
+    /// Samples `n` evenly spaced colors, the first and last color are at the ends of the gradient.
+    pub fn sample_n(&self, n: usize) -> Vec<Color> {
+        (0..n)
+            .map(|i| match n {
+                1 => 0.0,
+                _ => i as f32 / (n - 1) as f32,
+            })
+            .map(|t| self.sample(t))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample() {
+        let red = Color::new(255, 0, 0);
+        let blue = Color::new(0, 0, 255);
+        let gradient = Gradient::even([red, blue]);
+        assert_eq!(gradient.sample(0.0), red);
+        assert_eq!(gradient.sample(1.0), blue);
+        assert_eq!(gradient.sample(0.5), Color::new(128, 0, 128));
+        assert_eq!(
+            gradient.sample_n(3),
+            vec![red, Color::new(128, 0, 128), blue]
+        );
+        assert_eq!(gradient.sample_n(1), vec![red]);
+        assert!(gradient.sample_n(0).is_empty());
+    }
+
+    #[test]
+    fn test_unsorted_stops() {
+        let gradient = Gradient::new([
+            (1.0, Color::new(0, 0, 0)),
+            (0.0, Color::new(255, 255, 255)),
+            (0.5, Color::new(255, 0, 0)),
+        ]);
+        assert_eq!(gradient.sample(0.5), Color::new(255, 0, 0));
+        assert_eq!(gradient.sample(0.25), Color::new(255, 128, 128));
+        assert_eq!(gradient.sample(-1.0), Color::new(255, 255, 255));
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(Gradient::even::<Color>([]).sample(0.3), Color::default());
+    }
+
+    #[test]
+    fn test_oklab() {
+        let gradient = Gradient::even([Color::new(255, 0, 0), Color::new(0, 255, 0)])
+            .with_interpolation(Interpolation::OkLab);
+        let mid = gradient.sample(0.5);
+        // OKLab keeps the midpoint brighter than plain RGB interpolation
+        assert!(u16::from(mid.r) + u16::from(mid.g) > 256);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::Color;
+
+use super::to_u8;
+
+/// Color in the HSV (hue, saturation, value) color space.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Hsv {
+    /// Hue in degrees, from 0.0 to 360.0.
+    pub h: f32,
+    /// Saturation, from 0.0 to 1.0.
+    pub s: f32,
+    /// Value (brightness), from 0.0 to 1.0.
+    pub v: f32,
+}
+
+impl Hsv {
+    /// Creates a new HSV color. The hue wraps around, so `-90.0` equals `270.0`.
+    pub const fn new(h: f32, s: f32, v: f32) -> Self {
+        Self { h, s, v }
+    }
+}
+
+impl From<Hsv> for Color {
+    fn from(hsv: Hsv) -> Self {
+        let (s, v) = (hsv.s.clamp(0.0, 1.0), hsv.v.clamp(0.0, 1.0));
+        let c = v * s;
+        let (r, g, b) = hue_to_rgb(hsv.h, c);
+        let m = v - c;
+        Color::new(to_u8(r + m), to_u8(g + m), to_u8(b + m))
+    }
+}
+
+impl From<Color> for Hsv {
+    fn from(color: Color) -> Self {
+        let (h, max, min) = hue_max_min(color);
+        let s = match max > 0.0 {
+            true => (max - min) / max,
+            false => 0.0,
+        };
+        Self { h, s, v: max }
+    }
+}
+
+/// Color in the HSL (hue, saturation, lightness) color space.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Hsl {
+    /// Hue in degrees, from 0.0 to 360.0.
+    pub h: f32,
+    /// Saturation, from 0.0 to 1.0.
+    pub s: f32,
+    /// Lightness, from 0.0 (black) to 1.0 (white).
+    pub l: f32,
+}
+
+impl Hsl {
+    /// Creates a new HSL color. The hue wraps around, so `-90.0` equals `270.0`.
+    pub const fn new(h: f32, s: f32, l: f32) -> Self {
+        Self { h, s, l }
+    }
+}
+
+impl From<Hsl> for Color {
+    fn from(hsl: Hsl) -> Self {
+        let (s, l) = (hsl.s.clamp(0.0, 1.0), hsl.l.clamp(0.0, 1.0));
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let (r, g, b) = hue_to_rgb(hsl.h, c);
+        let m = l - c / 2.0;
+        Color::new(to_u8(r + m), to_u8(g + m), to_u8(b + m))
+    }
+}
+
+impl From<Color> for Hsl {
+    fn from(color: Color) -> Self {
+        let (h, max, min) = hue_max_min(color);
+        let l = f32::midpoint(max, min);
+        let s = match max - min > 0.0 {
+            true => (max - min) / (1.0 - (2.0 * l - 1.0).abs()),
+            false => 0.0,
+        };
+        Self { h, s, l }
+    }
+}
+
+/// Returns the `(r, g, b)` components for `hue` with chroma `c`, without the lightness offset.
+fn hue_to_rgb(hue: f32, c: f32) -> (f32, f32, f32) {
+    let h = hue.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    }
+}
+
+/// Returns the hue in degrees and the maximum and minimum channel values (`0.0..=1.0`) of `color`.
+fn hue_max_min(color: Color) -> (f32, f32, f32) {
+    let (r, g, b) = (
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+    );
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let h = if delta <= 0.0 {
+        0.0
+    } else if (max - r).abs() < f32::EPSILON {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if (max - g).abs() < f32::EPSILON {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    (h, max, min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hsv_to_rgb() {
+        assert_eq!(Color::from(Hsv::new(0.0, 1.0, 1.0)), Color::new(255, 0, 0));
+        assert_eq!(
+            Color::from(Hsv::new(120.0, 1.0, 1.0)),
+            Color::new(0, 255, 0)
+        );
+        assert_eq!(
+            Color::from(Hsv::new(240.0, 1.0, 1.0)),
+            Color::new(0, 0, 255)
+        );
+        assert_eq!(
+            Color::from(Hsv::new(360.0, 1.0, 1.0)),
+            Color::new(255, 0, 0)
+        );
+        assert_eq!(
+            Color::from(Hsv::new(-120.0, 1.0, 1.0)),
+            Color::new(0, 0, 255)
+        );
+        assert_eq!(
+            Color::from(Hsv::new(42.0, 0.0, 1.0)),
+            Color::new(255, 255, 255)
+        );
+    }
+
+    #[test]
+    fn test_hsl_to_rgb() {
+        assert_eq!(Color::from(Hsl::new(0.0, 1.0, 0.5)), Color::new(255, 0, 0));
+        assert_eq!(
+            Color::from(Hsl::new(0.0, 1.0, 1.0)),
+            Color::new(255, 255, 255)
+        );
+        assert_eq!(Color::from(Hsl::new(0.0, 1.0, 0.0)), Color::new(0, 0, 0));
+        assert_eq!(
+            Color::from(Hsl::new(180.0, 1.0, 0.25)),
+            Color::new(0, 128, 128)
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for color in [
+            Color::new(255, 128, 0),
+            Color::new(12, 200, 99),
+            Color::new(80, 80, 80),
+            Color::new(1, 2, 250),
+        ] {
+            assert_eq!(Color::from(Hsv::from(color)), color);
+            assert_eq!(Color::from(Hsl::from(color)), color);
+        }
+    }
+}
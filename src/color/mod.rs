@@ -0,0 +1,186 @@
+//! Color utilities.
+//!
+//! [`Color`] is an alias of [`rgb::RGB8`], this module adds conversions from and to other color spaces,
+//! blending, gamma correction, color temperature and gradients on top of it.
+//!
+//! All color types in this module implement `Into<Color>`, so they can be passed directly to
+//! methods such as [`crate::Controller::set_leds()`].
+//!
+//! # Example
+//!
+//! ```
+//! use openrgb2::{Color, color::{self, ColorExt, Gradient, Hsv}};
+//!
+//! let orange: Color = Hsv::new(30.0, 1.0, 1.0).into();
+//! let purple = color::parse("#800080").unwrap();
+//! let halfway = orange.lerp(purple, 0.5);
+//!
+//! let colors = Gradient::even([orange, purple]).sample_n(10);
+//! assert_eq!(colors.len(), 10);
+//! ```
+
+mod blend;
+mod gamma;
+mod gradient;
+mod hsv;
+mod named;
+mod oklab;
+mod temperature;
+
+pub use {blend::*, gamma::*, gradient::*, hsv::*, oklab::*, temperature::*};
+
+use crate::{Color, OpenRgbError, OpenRgbResult};
+
+/// Extension methods for [`Color`].
+pub trait ColorExt: Sized {
+    /// Linearly interpolates between `self` and `other`.
+    ///
+    /// `t` is clamped to `0.0..=1.0`, where `0.0` returns `self` and `1.0` returns `other`.
+    fn lerp(self, other: Self, t: f32) -> Self;
+
+    /// Scales the brightness of this color by `factor`, which is clamped to `0.0..=1.0`.
+    fn scale(self, factor: f32) -> Self;
+
+    /// Blends `top` on top of this color using `mode`.
+    fn blend(self, top: Self, mode: BlendMode) -> Self;
+
+    /// Converts this color to HSV.
+    fn to_hsv(self) -> Hsv;
+
+    /// Converts this color to HSL.
+    fn to_hsl(self) -> Hsl;
+
+    /// Converts this color to `OKLab`.
+    fn to_oklab(self) -> OkLab;
+
+    /// Formats this color as `#RRGGBB`.
+    fn to_hex(self) -> String;
+}
+
+impl ColorExt for Color {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| {
+            let v = f32::from(a) + (f32::from(b) - f32::from(a)) * t;
+            v.round() as u8
+        };
+        Color::new(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+        )
+    }
+
+    fn scale(self, factor: f32) -> Self {
+        Color::default().lerp(self, factor)
+    }
+
+    fn blend(self, top: Self, mode: BlendMode) -> Self {
+        mode.blend(self, top)
+    }
+
+    fn to_hsv(self) -> Hsv {
+        Hsv::from(self)
+    }
+
+    fn to_hsl(self) -> Hsl {
+        Hsl::from(self)
+    }
+
+    fn to_oklab(self) -> OkLab {
+        OkLab::from(self)
+    }
+
+    fn to_hex(self) -> String {
+        format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+    }
+}
+
+/// Parses a color from a string.
+///
+/// Supported formats are `#RRGGBB`, `#RGB` (the `#` is optional) and CSS color names, such as `rebeccapurple`.
+///
+/// # Errors
+///
+/// Returns an error if `s` is not a valid hex color or a known color name.
+///
+/// # Example
+///
+/// ```
+/// use openrgb2::{Color, color};
+///
+/// assert_eq!(color::parse("#FF8000").unwrap(), Color::new(255, 128, 0));
+/// assert_eq!(color::parse("f80").unwrap(), Color::new(255, 136, 0));
+/// assert_eq!(color::parse("Teal").unwrap(), Color::new(0, 128, 128));
+/// ```
+pub fn parse(s: &str) -> OpenRgbResult<Color> {
+    let s = s.trim();
+    if let Some(color) = named::lookup(s) {
+        return Ok(color);
+    }
+
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    let invalid = || OpenRgbError::ParseError(format!("Invalid color: {s:?}"));
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let channel = |range: std::ops::Range<usize>| u8::from_str_radix(&hex[range], 16);
+    let color = match hex.len() {
+        6 => Color::new(
+            channel(0..2).map_err(|_| invalid())?,
+            channel(2..4).map_err(|_| invalid())?,
+            channel(4..6).map_err(|_| invalid())?,
+        ),
+        // #RGB is shorthand for #RRGGBB
+        3 => Color::new(
+            channel(0..1).map_err(|_| invalid())? * 17,
+            channel(1..2).map_err(|_| invalid())? * 17,
+            channel(2..3).map_err(|_| invalid())? * 17,
+        ),
+        _ => return Err(invalid()),
+    };
+    Ok(color)
+}
+
+/// Converts a `0.0..=1.0` channel value to a `u8`.
+pub(crate) fn to_u8(v: f32) -> u8 {
+    (v.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lerp() {
+        let black = Color::new(0, 0, 0);
+        let white = Color::new(255, 255, 255);
+        assert_eq!(black.lerp(white, 0.0), black);
+        assert_eq!(black.lerp(white, 1.0), white);
+        assert_eq!(black.lerp(white, 0.5), Color::new(128, 128, 128));
+        assert_eq!(black.lerp(white, 2.0), white);
+        assert_eq!(white.scale(0.0), black);
+    }
+
+    #[test]
+    fn test_parse() -> OpenRgbResult<()> {
+        assert_eq!(parse("#102030")?, Color::new(16, 32, 48));
+        assert_eq!(parse("102030")?, Color::new(16, 32, 48));
+        assert_eq!(parse("#fff")?, Color::new(255, 255, 255));
+        assert_eq!(parse(" Red ")?, Color::new(255, 0, 0));
+        assert_eq!(parse("rebeccapurple")?, Color::new(102, 51, 153));
+        assert!(parse("#12345").is_err());
+        assert!(parse("#GGGGGG").is_err());
+        assert!(parse("not a color").is_err());
+        assert!(parse("").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_hex() -> OpenRgbResult<()> {
+        let color = Color::new(1, 171, 255);
+        assert_eq!(color.to_hex(), "#01ABFF");
+        assert_eq!(parse(&color.to_hex())?, color);
+        Ok(())
+    }
+}
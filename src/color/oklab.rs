@@ -0,0 +1,110 @@
+use crate::Color;
+
+use super::to_u8;
+
+/// Color in the perceptual `OKLab` color space.
+///
+/// Interpolating in `OKLab` gives smoother gradients than interpolating RGB values.
+/// See <https://bottosson.github.io/posts/oklab/>.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct OkLab {
+    /// Perceived lightness, from 0.0 to 1.0.
+    pub l: f32,
+    /// Green/red axis.
+    pub a: f32,
+    /// Blue/yellow axis.
+    pub b: f32,
+}
+
+impl OkLab {
+    /// Creates a new `OKLab` color.
+    pub const fn new(l: f32, a: f32, b: f32) -> Self {
+        Self { l, a, b }
+    }
+
+    /// Linearly interpolates between `self` and `other`, `t` is clamped to `0.0..=1.0`.
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        Self {
+            l: self.l + (other.l - self.l) * t,
+            a: self.a + (other.a - self.a) * t,
+            b: self.b + (other.b - self.b) * t,
+        }
+    }
+}
+
+impl From<Color> for OkLab {
+    fn from(color: Color) -> Self {
+        let (r, g, b) = (
+            srgb_to_linear(color.r),
+            srgb_to_linear(color.g),
+            srgb_to_linear(color.b),
+        );
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+        Self {
+            l: 0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            a: 1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            b: 0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        }
+    }
+}
+
+impl From<OkLab> for Color {
+    fn from(lab: OkLab) -> Self {
+        let l = (lab.l + 0.396_337_78 * lab.a + 0.215_803_76 * lab.b).powi(3);
+        let m = (lab.l - 0.105_561_346 * lab.a - 0.063_854_17 * lab.b).powi(3);
+        let s = (lab.l - 0.089_484_18 * lab.a - 1.291_485_5 * lab.b).powi(3);
+        let r = 4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s;
+        let g = -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s;
+        let b = -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s;
+        Color::new(linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b))
+    }
+}
+
+/// Converts an sRGB encoded channel to linear light.
+pub(crate) fn srgb_to_linear(c: u8) -> f32 {
+    let c = f32::from(c) / 255.0;
+    match c <= 0.040_45 {
+        true => c / 12.92,
+        false => ((c + 0.055) / 1.055).powf(2.4),
+    }
+}
+
+/// Converts a linear light channel to sRGB encoding.
+pub(crate) fn linear_to_srgb(c: f32) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let c = match c <= 0.003_130_8 {
+        true => c * 12.92,
+        false => 1.055 * c.powf(1.0 / 2.4) - 0.055,
+    };
+    to_u8(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_white_black() {
+        let white = OkLab::from(Color::new(255, 255, 255));
+        assert!((white.l - 1.0).abs() < 1e-3);
+        assert!(white.a.abs() < 1e-3 && white.b.abs() < 1e-3);
+        let black = OkLab::from(Color::new(0, 0, 0));
+        assert!(black.l.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for color in [
+            Color::new(255, 0, 0),
+            Color::new(0, 255, 0),
+            Color::new(0, 0, 255),
+            Color::new(12, 200, 99),
+            Color::new(128, 128, 128),
+        ] {
+            assert_eq!(Color::from(OkLab::from(color)), color);
+        }
+    }
+}
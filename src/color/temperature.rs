@@ -0,0 +1,47 @@
+use crate::Color;
+
+/// Returns the color of a black body at `kelvin` degrees, which is useful for white balance.
+///
+/// Warm white is around 2700K, daylight around 6500K.
+/// The input is clamped to `1000..=40000`.
+///
+/// Uses the approximation by [Tanner Helland](https://tannerhelland.com/2012/09/18/convert-temperature-rgb-algorithm-code.html).
+pub fn kelvin(kelvin: f32) -> Color {
+    let t = kelvin.clamp(1000.0, 40000.0) / 100.0;
+    let r = match t <= 66.0 {
+        true => 255.0,
+        false => 329.698_73 * (t - 60.0).powf(-0.133_204_76),
+    };
+    let g = match t <= 66.0 {
+        true => 99.470_8 * t.ln() - 161.119_57,
+        false => 288.122_16 * (t - 60.0).powf(-0.075_514_846),
+    };
+    let b = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        138.517_73 * (t - 10.0).ln() - 305.044_8
+    };
+    let to_u8 = |v: f32| v.clamp(0.0, 255.0).round() as u8;
+    Color::new(to_u8(r), to_u8(g), to_u8(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kelvin() {
+        let warm = kelvin(2700.0);
+        assert_eq!(warm.r, 255);
+        assert!(warm.g < 200 && warm.b < 150);
+
+        let daylight = kelvin(6600.0);
+        assert_eq!(daylight.r, 255);
+        assert!(daylight.g > 240 && daylight.b > 240);
+
+        let cold = kelvin(20000.0);
+        assert!(cold.b == 255 && cold.r < 200);
+    }
+}
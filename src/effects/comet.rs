@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use crate::{Color, color::ColorExt};
+
+use super::{Effect, LedPosition};
+
+/// A bright head with a fading tail that chases along the LEDs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comet {
+    /// Color of the comet.
+    pub color: Color,
+    /// Color of the LEDs the comet is not on.
+    pub background: Color,
+    /// Number of passes per second. Negative values reverse the direction.
+    pub speed: f32,
+    /// Length of the tail as a fraction of the zone length.
+    pub tail: f32,
+}
+
+impl Comet {
+    /// Creates a comet with the given `color`, passing by once per second.
+    pub fn new<C: Into<Color>>(color: C) -> Self {
+        Self {
+            color: color.into(),
+            background: Color::default(),
+            speed: 1.0,
+            tail: 0.25,
+        }
+    }
+}
+
+impl Effect for Comet {
+    fn color_at(&self, pos: &LedPosition, t: Duration) -> Color {
+        let head = (t.as_secs_f32() * self.speed).rem_euclid(1.0);
+        // distance behind the head, in the direction of travel
+        let behind = match self.speed < 0.0 {
+            true => pos.x - head,
+            false => head - pos.x,
+        }
+        .rem_euclid(1.0);
+        // the head itself covers one LED
+        let head_width = 1.0 / pos.count.max(1) as f32;
+        let level = match behind {
+            d if d < head_width || 1.0 - d < head_width => 1.0,
+            d if self.tail > 0.0 && d <= self.tail => 1.0 - d / self.tail,
+            _ => 0.0,
+        };
+        self.background.lerp(self.color, level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comet() {
+        let effect = Comet::new(Color::new(255, 0, 0));
+        let t = Duration::from_millis(500);
+        // head is halfway
+        assert_eq!(
+            effect.color_at(&LedPosition::linear(5, 11), t),
+            Color::new(255, 0, 0)
+        );
+        // in front of the head is dark
+        assert_eq!(
+            effect.color_at(&LedPosition::linear(8, 11), t),
+            Color::new(0, 0, 0)
+        );
+        // tail is dimmer
+        let tail = effect.color_at(&LedPosition::linear(4, 11), t);
+        assert!(tail.r > 0 && tail.r < 255);
+    }
+}
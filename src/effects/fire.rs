@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use crate::{Color, color::ColorExt};
+
+use super::{Effect, LedPosition, noise};
+
+/// Flickering flames.
+///
+/// On linear zones the flames rise from the first LED, on matrix zones from the bottom row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fire {
+    /// How fast the flames flicker.
+    pub speed: f32,
+    /// Height of the flames, from 0.0 to 1.0.
+    pub height: f32,
+    /// Seed for the random pattern.
+    pub seed: u32,
+}
+
+impl Default for Fire {
+    fn default() -> Self {
+        Self {
+            speed: 1.5,
+            height: 0.8,
+            seed: 0,
+        }
+    }
+}
+
+impl Fire {
+    /// Maps a heat value (`0.0..=1.0`) to a color, going from black through red and yellow to white.
+    fn heat_color(heat: f32) -> Color {
+        const PALETTE: [Color; 4] = [
+            Color::new(0, 0, 0),
+            Color::new(255, 0, 0),
+            Color::new(255, 160, 0),
+            Color::new(255, 255, 200),
+        ];
+        let scaled = heat.clamp(0.0, 1.0) * (PALETTE.len() - 1) as f32;
+        let idx = (scaled as usize).min(PALETTE.len() - 2);
+        PALETTE[idx].lerp(PALETTE[idx + 1], scaled - idx as f32)
+    }
+}
+
+impl Effect for Fire {
+    fn color_at(&self, pos: &LedPosition, t: Duration) -> Color {
+        // height above the base of the fire and position across it
+        let (up, across) = match pos.is_matrix {
+            true => (1.0 - pos.y, pos.x),
+            false => (pos.x, 0.0),
+        };
+        let t = t.as_secs_f32() * self.speed;
+        let flicker = noise(up * 6.0 - t * 3.0 + across * 11.0, self.seed);
+        let sway = noise(across * 4.0 + t, self.seed.wrapping_add(1));
+        let height = self.height.clamp(0.01, 1.0) * (0.6 + 0.4 * sway);
+        let heat = (1.0 - up / height) * (0.55 + 0.45 * flicker);
+        Self::heat_color(heat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heat_color() {
+        assert_eq!(Fire::heat_color(0.0), Color::new(0, 0, 0));
+        assert_eq!(Fire::heat_color(1.0), Color::new(255, 255, 200));
+        assert_eq!(Fire::heat_color(-1.0), Color::new(0, 0, 0));
+    }
+
+    #[test]
+    fn test_fire_base_is_hot() {
+        let fire = Fire::default();
+        let t = Duration::from_millis(300);
+        let base = fire.color_at(&LedPosition::linear(0, 20), t);
+        let top = fire.color_at(&LedPosition::linear(19, 20), t);
+        assert!(base.r > 0);
+        assert_eq!(top, Color::new(0, 0, 0));
+    }
+
+    #[test]
+    fn test_fire_matrix_rises_from_bottom_row() {
+        let fire = Fire::default();
+        let (rows, columns) = (2, 8);
+        let count = rows * columns;
+        let row_heat = |row: usize, t: Duration| -> u32 {
+            (0..columns)
+                .map(|col| {
+                    let pos = LedPosition {
+                        index: row * columns + col,
+                        count,
+                        x: col as f32 / (columns - 1) as f32,
+                        y: row as f32 / (rows - 1) as f32,
+                        z: 0.0,
+                        is_matrix: true,
+                    };
+                    let c = fire.color_at(&pos, t);
+                    u32::from(c.r) + u32::from(c.g) + u32::from(c.b)
+                })
+                .sum()
+        };
+        for ms in [0, 300, 1200, 5000] {
+            let t = Duration::from_millis(ms);
+            assert!(row_heat(0, t) < row_heat(1, t), "top row hotter at {ms}ms");
+        }
+    }
+}
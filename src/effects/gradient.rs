@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use crate::{Color, color::ColorExt};
+
+use super::{Effect, LedPosition};
+
+/// Scrolls a looping gradient along the LEDs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradientScroll {
+    /// Colors of the gradient, evenly spaced. The last color blends back into the first.
+    pub colors: Vec<Color>,
+    /// Number of times the gradient scrolls by per second. Negative values reverse the direction.
+    pub speed: f32,
+    /// Number of times the gradient fits over the length of a zone.
+    pub repeat: f32,
+}
+
+impl GradientScroll {
+    /// Creates a scrolling gradient with the given colors.
+    pub fn new<C: Into<Color>>(colors: impl IntoIterator<Item = C>) -> Self {
+        Self {
+            colors: colors.into_iter().map(Into::into).collect(),
+            speed: 0.25,
+            repeat: 1.0,
+        }
+    }
+}
+
+impl Effect for GradientScroll {
+    fn color_at(&self, pos: &LedPosition, t: Duration) -> Color {
+        let n = self.colors.len();
+        if n == 0 {
+            return Color::default();
+        }
+        let p = (pos.x * self.repeat - t.as_secs_f32() * self.speed).rem_euclid(1.0) * n as f32;
+        let idx = (p as usize).min(n - 1);
+        self.colors[idx].lerp(self.colors[(idx + 1) % n], p - idx as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gradient() {
+        let red = Color::new(255, 0, 0);
+        let blue = Color::new(0, 0, 255);
+        let effect = GradientScroll::new([red, blue]);
+        assert_eq!(
+            effect.color_at(&LedPosition::linear(0, 5), Duration::ZERO),
+            red
+        );
+        assert_eq!(
+            effect.color_at(&LedPosition::linear(1, 5), Duration::ZERO),
+            Color::new(128, 0, 128)
+        );
+        assert_eq!(
+            effect.color_at(&LedPosition::linear(2, 5), Duration::ZERO),
+            blue
+        );
+        // scrolled by half a gradient
+        assert_eq!(
+            effect.color_at(&LedPosition::linear(0, 5), Duration::from_secs(2)),
+            blue
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Client-side lighting effects.
+//!
+//! Effects are functions of the position of an LED and time, which makes them usable on any
+//! [`Controller`], [`Zone`], [`Segment`] or [`ControllerGroup`].
+//! Rendering an effect results in a [`Command`] or [`CommandGroup`] that still has to be executed.
+//!
+//! Zone geometry is respected: LEDs in linear zones are spread out over the horizontal axis,
+//! LEDs in matrix zones (e.g. keyboards) are placed on their position in the matrix.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use openrgb2::{OpenRgbClient, OpenRgbResult, effects::{Effect, RainbowWave}};
+//! # use std::time::Instant;
+//! # async fn example() -> OpenRgbResult<()> {
+//! let client = OpenRgbClient::connect().await?;
+//! let group = client.get_all_controllers().await?;
+//! group.set_controllable_mode().await?;
+//!
+//! let effect = RainbowWave::default();
+//! let start = Instant::now();
+//! loop {
+//!     effect.render_group(&group, start.elapsed()).execute().await?;
+//!     tokio::time::sleep(std::time::Duration::from_millis(16)).await;
+//! }
+//! # }
+//! ```
+
+mod comet;
+mod fire;
+mod gradient;
+mod pulse;
+mod rainbow;
+mod twinkle;
+
+pub use {comet::*, fire::*, gradient::*, pulse::*, rainbow::*, twinkle::*};
+
+use std::time::Duration;
+
+use crate::{
+    Color, Command, CommandGroup, Controller, ControllerGroup, Segment, Zone, ZoneType,
+    color::Hsv,
+    layout::{Layout, Placement},
+};
+
+/// Position of an LED in the area an effect is rendered to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LedPosition {
+    /// Index of this LED in the rendered area (zone or segment).
+    pub index: usize,
+    /// Number of LEDs in the rendered area.
+    pub count: usize,
+    /// Horizontal position, from 0.0 (first LED or left column) to 1.0 (last LED or right column).
+    pub x: f32,
+    /// Vertical position, from 0.0 (top row) to 1.0 (bottom row). Always 0.0 for non-matrix zones.
+    pub y: f32,
+    /// Depth position, from 0.0 to 1.0. Only used when rendering a [`Layout`].
+    pub z: f32,
+    /// True if the LED is placed on a 2D area, so `y` is meaningful:
+    /// the LED is part of a matrix zone with more than one row,
+    /// or of a [`Layout`] that spans vertically.
+    pub is_matrix: bool,
+}
+
+impl LedPosition {
+    /// Position of LED `index` in a linear area of `count` LEDs.
+    pub fn linear(index: usize, count: usize) -> Self {
+        Self {
+            index,
+            count,
+            x: normalise(index, count),
+            y: 0.0,
+            z: 0.0,
+            is_matrix: false,
+        }
+    }
+}
+
+/// A time based effect that can be rendered to any controller, zone or segment.
+///
+/// Only [`Effect::color_at()`] has to be implemented, the render methods are provided.
+pub trait Effect {
+    /// Returns the color of the LED at `pos`, `t` after the effect started.
+    fn color_at(&self, pos: &LedPosition, t: Duration) -> Color;
+
+    /// Returns the colors of all LEDs of `controller`, zone by zone.
+    fn controller_colors(&self, controller: &Controller, t: Duration) -> Vec<Color> {
+        let mut colors = Vec::with_capacity(controller.num_leds());
+        for zone in controller.get_all_zones() {
+            colors.extend(self.zone_colors(&zone, t));
+        }
+        colors
+    }
+
+    /// Returns the colors of all LEDs in `zone`.
+    fn zone_colors(&self, zone: &Zone<'_>, t: Duration) -> Vec<Color> {
+        zone_positions(zone)
+            .iter()
+            .map(|pos| self.color_at(pos, t))
+            .collect()
+    }
+
+    /// Renders this effect to all LEDs of `controller`.
+    fn render_controller<'a>(&self, controller: &'a Controller, t: Duration) -> Command<'a> {
+        let mut cmd = controller.cmd();
+        cmd.set_leds(self.controller_colors(controller, t))
+            .expect("Effect rendered too many colors");
+        cmd
+    }
+
+    /// Renders this effect to the LEDs of `zone`.
+    ///
+    /// LEDs outside of the zone are not part of the returned command.
+    fn render_zone<'a>(&self, zone: &Zone<'a>, t: Duration) -> Command<'a> {
+        let mut cmd = zone.cmd();
+        for (led, color) in zone.led_iter().zip(self.zone_colors(zone, t)) {
+            cmd.set_led(led.id(), color).expect("Led index incorrect");
+        }
+        cmd
+    }
+
+    /// Renders this effect to the LEDs of `segment`, treating it as a linear strip.
+    ///
+    /// LEDs outside of the segment are not part of the returned command.
+    fn render_segment<'a>(&self, segment: &'a Segment<'a>, t: Duration) -> Command<'a> {
+        let count = segment.num_leds();
+        let mut cmd = segment.cmd();
+        for (idx, led) in segment.led_iter().enumerate() {
+            let color = self.color_at(&LedPosition::linear(idx, count), t);
+            cmd.set_led(led.id(), color).expect("Led index incorrect");
+        }
+        cmd
+    }
+
+    /// Renders this effect to every controller in `group`.
+    fn render_group<'a>(&self, group: &'a ControllerGroup, t: Duration) -> CommandGroup<'a> {
+        let mut cmd = group.cmd();
+        for controller in group {
+            cmd.set_controller_leds(controller, self.controller_colors(controller, t))
+                .expect("Controller is part of group");
+        }
+        cmd
+    }
+
+    /// Renders this effect to every controller in `group`, sampling it at the LED positions in `layout`.
+    ///
+    /// Positions are normalised over the whole group, so the effect spans all controllers.
+    fn render_layout<'a>(
+        &self,
+        layout: &Layout,
+        group: &'a ControllerGroup,
+        t: Duration,
+    ) -> CommandGroup<'a> {
+        let mut cmd = group.cmd();
+        for (controller, positions) in group.iter().zip(layout.led_positions(group)) {
+            let colors = positions.iter().map(|pos| self.color_at(pos, t));
+            cmd.set_controller_leds(controller, colors)
+                .expect("Controller is part of group");
+        }
+        cmd
+    }
+}
+
+/// Computes the position of every LED in `zone`, ordered by LED index in the zone.
+///
+/// LEDs of matrix zones are placed on their position in the matrix,
+/// LEDs that are missing from the matrix map are placed linearly.
+pub(crate) fn zone_positions(zone: &Zone<'_>) -> Vec<LedPosition> {
+    let count = zone.num_leds();
+    let is_matrix = zone.zone_type() == ZoneType::Matrix
+        && zone.matrix_data().is_some_and(|m| m.num_rows() > 1);
+    Placement::default()
+        .points(zone)
+        .into_iter()
+        .enumerate()
+        .map(|(index, p)| LedPosition {
+            index,
+            count,
+            x: p.x,
+            y: p.y,
+            z: 0.0,
+            is_matrix,
+        })
+        .collect()
+}
+
+/// Maps `idx` in `0..count` to `0.0..=1.0`.
+pub(crate) fn normalise(idx: usize, count: usize) -> f32 {
+    match count {
+        0 | 1 => 0.0,
+        _ => idx as f32 / (count - 1) as f32,
+    }
+}
+
+/// Integer hash, used as a stateless source of randomness.
+fn hash(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb_352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846c_a68b);
+    x ^= x >> 16;
+    x
+}
+
+/// Pseudo random value in `0.0..=1.0` for the given inputs.
+fn random(a: u32, b: u32) -> f32 {
+    hash(a ^ hash(b).rotate_left(13)) as f32 / u32::MAX as f32
+}
+
+/// Color for a hue in turns (`0.0..1.0`) rather than degrees.
+fn hue(turns: f32, saturation: f32, value: f32) -> Color {
+    Hsv::new(turns * 360.0, saturation, value).into()
+}
+
+/// Smooth 1D value noise in `0.0..=1.0`.
+fn noise(x: f32, seed: u32) -> f32 {
+    let i = x.floor();
+    let f = x - i;
+    let f = f * f * (3.0 - 2.0 * f);
+    let i = i as i32 as u32;
+    let (a, b) = (random(i, seed), random(i.wrapping_add(1), seed));
+    a + (b - a) * f
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_position() {
+        assert!((LedPosition::linear(0, 1).x).abs() < f32::EPSILON);
+        assert!((LedPosition::linear(4, 5).x - 1.0).abs() < f32::EPSILON);
+        assert!((LedPosition::linear(2, 5).x - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_noise_range() {
+        for i in 0..1000 {
+            let n = noise(i as f32 * 0.37, 7);
+            assert!((0.0..=1.0).contains(&n));
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use crate::{Color, color::ColorExt};
+
+use super::{Effect, LedPosition};
+
+/// Fades all LEDs in and out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breathing {
+    /// Color at the peak of a breath.
+    pub color: Color,
+    /// Duration of a single breath.
+    pub period: Duration,
+    /// Brightness at the bottom of a breath, from 0.0 to 1.0.
+    pub min_brightness: f32,
+}
+
+impl Breathing {
+    /// Creates a breathing effect with the given `color`, taking 4 seconds per breath.
+    pub fn new<C: Into<Color>>(color: C) -> Self {
+        Self {
+            color: color.into(),
+            period: Duration::from_secs(4),
+            min_brightness: 0.0,
+        }
+    }
+}
+
+impl Effect for Breathing {
+    fn color_at(&self, _pos: &LedPosition, t: Duration) -> Color {
+        let phase = match self.period.is_zero() {
+            true => 0.0,
+            false => t.as_secs_f32() / self.period.as_secs_f32(),
+        };
+        let level = 0.5 - 0.5 * (phase * TAU).cos();
+        let min = self.min_brightness.clamp(0.0, 1.0);
+        self.color.scale(min + (1.0 - min) * level)
+    }
+}
+
+/// Flashes all LEDs on and off.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Strobe {
+    /// Color while the strobe is on.
+    pub color: Color,
+    /// Color while the strobe is off.
+    pub background: Color,
+    /// Number of flashes per second.
+    pub frequency: f32,
+    /// Fraction of a flash that the strobe is on, from 0.0 to 1.0.
+    pub duty_cycle: f32,
+}
+
+impl Strobe {
+    /// Creates a strobe with the given `color`, flashing 10 times per second.
+    pub fn new<C: Into<Color>>(color: C) -> Self {
+        Self {
+            color: color.into(),
+            background: Color::default(),
+            frequency: 10.0,
+            duty_cycle: 0.5,
+        }
+    }
+}
+
+impl Effect for Strobe {
+    fn color_at(&self, _pos: &LedPosition, t: Duration) -> Color {
+        match (t.as_secs_f32() * self.frequency).fract() < self.duty_cycle {
+            true => self.color,
+            false => self.background,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breathing() {
+        let effect = Breathing::new(Color::new(200, 100, 0));
+        let pos = LedPosition::linear(0, 1);
+        assert_eq!(effect.color_at(&pos, Duration::ZERO), Color::new(0, 0, 0));
+        assert_eq!(
+            effect.color_at(&pos, Duration::from_secs(2)),
+            Color::new(200, 100, 0)
+        );
+        assert_eq!(
+            effect.color_at(&pos, Duration::from_secs(4)),
+            Color::new(0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_strobe() {
+        let effect = Strobe::new(Color::new(255, 255, 255));
+        let pos = LedPosition::linear(0, 1);
+        assert_eq!(
+            effect.color_at(&pos, Duration::from_millis(20)),
+            Color::new(255, 255, 255)
+        );
+        assert_eq!(
+            effect.color_at(&pos, Duration::from_millis(70)),
+            Color::new(0, 0, 0)
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use crate::Color;
+
+use super::{Effect, LedPosition, hue};
+
+/// A rainbow that moves along the LEDs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RainbowWave {
+    /// Number of times the wave passes by per second. Negative values reverse the direction.
+    pub speed: f32,
+    /// Number of rainbows that fit over the length of a zone.
+    pub repeat: f32,
+    /// Saturation of the colors, from 0.0 to 1.0.
+    pub saturation: f32,
+    /// Brightness of the colors, from 0.0 to 1.0.
+    pub brightness: f32,
+}
+
+impl Default for RainbowWave {
+    fn default() -> Self {
+        Self {
+            speed: 0.25,
+            repeat: 1.0,
+            saturation: 1.0,
+            brightness: 1.0,
+        }
+    }
+}
+
+impl Effect for RainbowWave {
+    fn color_at(&self, pos: &LedPosition, t: Duration) -> Color {
+        let turns = pos.x * self.repeat - t.as_secs_f32() * self.speed;
+        hue(turns, self.saturation, self.brightness)
+    }
+}
+
+/// Cycles all LEDs through the color spectrum at the same time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorCycle {
+    /// Number of full cycles per second.
+    pub speed: f32,
+    /// Saturation of the colors, from 0.0 to 1.0.
+    pub saturation: f32,
+    /// Brightness of the colors, from 0.0 to 1.0.
+    pub brightness: f32,
+}
+
+impl Default for ColorCycle {
+    fn default() -> Self {
+        Self {
+            speed: 0.1,
+            saturation: 1.0,
+            brightness: 1.0,
+        }
+    }
+}
+
+impl Effect for ColorCycle {
+    fn color_at(&self, _pos: &LedPosition, t: Duration) -> Color {
+        hue(
+            t.as_secs_f32() * self.speed,
+            self.saturation,
+            self.brightness,
+        )
+    }
+}
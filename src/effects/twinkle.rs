@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use crate::{Color, color::ColorExt};
+
+use super::{Effect, LedPosition, hue, random};
+
+/// Randomly fades LEDs in and out, like stars.
+///
+/// The effect is stateless: whether an LED twinkles is derived from its index and the time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Twinkle {
+    /// Color of the stars, or `None` to give every star a random color.
+    pub color: Option<Color>,
+    /// Color of the LEDs that are not twinkling.
+    pub background: Color,
+    /// Fraction of LEDs that twinkle at the same time, from 0.0 to 1.0.
+    pub density: f32,
+    /// Time a single star takes to fade in and out.
+    pub duration: Duration,
+    /// Seed for the random pattern.
+    pub seed: u32,
+}
+
+impl Default for Twinkle {
+    fn default() -> Self {
+        Self {
+            color: None,
+            background: Color::default(),
+            density: 0.2,
+            duration: Duration::from_secs(1),
+            seed: 0,
+        }
+    }
+}
+
+impl Effect for Twinkle {
+    fn color_at(&self, pos: &LedPosition, t: Duration) -> Color {
+        if self.duration.is_zero() {
+            return self.background;
+        }
+        let index = pos.index as u32;
+        // offset every LED, so they don't all start at the same time
+        let local = t.as_secs_f32() / self.duration.as_secs_f32() + random(index, self.seed);
+        let cycle = local.floor() as u32;
+        if random(index, cycle ^ self.seed.rotate_left(7)) > self.density {
+            return self.background;
+        }
+
+        let level = 1.0 - (2.0 * local.fract() - 1.0).abs();
+        let color = self
+            .color
+            .unwrap_or_else(|| hue(random(cycle, index ^ self.seed), 1.0, 1.0));
+        self.background.lerp(color, level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_density() {
+        let none = Twinkle {
+            density: 0.0,
+            ..Default::default()
+        };
+        let t = Duration::from_millis(1234);
+        for i in 0..50 {
+            assert_eq!(
+                none.color_at(&LedPosition::linear(i, 50), t),
+                Color::default()
+            );
+        }
+
+        let all = Twinkle {
+            color: Some(Color::new(255, 255, 255)),
+            density: 1.0,
+            ..Default::default()
+        };
+        let lit = (0..50)
+            .filter(|&i| all.color_at(&LedPosition::linear(i, 50), t) != Color::default())
+            .count();
+        assert!(lit > 40);
+    }
+}
@@ -48,4 +48,19 @@ pub enum OpenRgbError {
     /// Command was given invalid parameters
     #[error("Invalid command: {0}")]
     CommandError(String),
+
+    /// Failed to parse user provided input, such as a color.
+    #[error("Failed to parse: {0}")]
+    ParseError(String),
+
+    /// Failed to read or write a file.
+    #[error("Failed to access file {path:?}")]
+    FileError {
+        /// Path of the file.
+        path: String,
+
+        /// Source error.
+        #[source]
+        source: std::io::Error,
+    },
 }
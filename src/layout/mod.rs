@@ -0,0 +1,250 @@
+//! Spatial layout of LEDs across controllers.
+//!
+//! A [`Layout`] assigns every LED of every controller a [`Point`] in a shared coordinate space,
+//! for example your desk or room in centimeters.
+//! Matrix zones (e.g. keyboards) are placed using their matrix map,
+//! linear zones (e.g. strips) can be placed along a path.
+//!
+//! Effects can then be sampled spatially using [`crate::effects::Effect::render_layout()`],
+//! so a wave moves across the keyboard, case and monitor strip as one.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use openrgb2::{OpenRgbClient, OpenRgbResult, effects::{Effect, RainbowWave}};
+//! # use openrgb2::layout::{Layout, Placement, Point};
+//! # use std::time::Duration;
+//! # async fn example() -> OpenRgbResult<()> {
+//! let client = OpenRgbClient::connect().await?;
+//! let group = client.get_all_controllers().await?;
+//! let keyboard = group.get_controller(0)?;
+//! let strip = group.get_controller(1)?;
+//!
+//! let mut layout = Layout::new();
+//! // 45x15cm keyboard in front of the monitor
+//! layout.place_zone(
+//!     &keyboard.get_zone(0)?,
+//!     Placement::Grid {
+//!         origin: Point::new(0.0, 30.0, 0.0),
+//!         across: Point::new(45.0, 0.0, 0.0),
+//!         down: Point::new(0.0, 15.0, 0.0),
+//!     },
+//! );
+//! // strip running along the left, top and right edge of the monitor
+//! layout.place_zone(
+//!     &strip.get_zone(0)?,
+//!     Placement::Path(vec![
+//!         Point::new(-10.0, 0.0, 0.0),
+//!         Point::new(-10.0, -35.0, 0.0),
+//!         Point::new(55.0, -35.0, 0.0),
+//!         Point::new(55.0, 0.0, 0.0),
+//!     ]),
+//! );
+//!
+//! RainbowWave::default()
+//!     .render_layout(&layout, &group, Duration::ZERO)
+//!     .execute()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod placement;
+
+pub use placement::*;
+
+use std::collections::HashMap;
+use std::ops::{Add, Mul, Sub};
+
+use crate::{Controller, ControllerGroup, DeviceId, Zone, effects::LedPosition};
+
+/// A point (or vector) in the layout space.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Point {
+    /// Horizontal coordinate, increasing to the right.
+    pub x: f32,
+    /// Vertical coordinate, increasing downwards.
+    pub y: f32,
+    /// Depth coordinate, increasing away from the viewer.
+    pub z: f32,
+}
+
+impl Point {
+    /// The point `(0, 0, 0)`.
+    pub const ORIGIN: Self = Self::new(0.0, 0.0, 0.0);
+
+    /// Creates a new point.
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns the distance between `self` and `other`.
+    pub fn distance(self, other: Self) -> f32 {
+        let d = other - self;
+        (d.x * d.x + d.y * d.y + d.z * d.z).sqrt()
+    }
+
+    /// Linearly interpolates between `self` and `other`, `t` is not clamped.
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+
+    fn min(self, other: Self) -> Self {
+        Self::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    fn max(self, other: Self) -> Self {
+        Self::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<f32> for Point {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+/// Positions of all LEDs in a shared coordinate space.
+///
+/// Zones are identified by the [`DeviceId`] of their controller and their name,
+/// so a layout keeps working after a rescan changed the controller IDs.
+/// Zones that were not placed use [`Placement::default()`], a unit square at the origin.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Layout {
+    placements: HashMap<(DeviceId, String), Placement>,
+}
+
+impl Layout {
+    /// Creates an empty layout.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Places `zone` in this layout, replacing its previous placement.
+    pub fn place_zone(&mut self, zone: &Zone<'_>, placement: Placement) {
+        self.placements.insert(zone_key(zone), placement);
+    }
+
+    /// Removes the placement of `zone` and returns it.
+    pub fn remove_zone(&mut self, zone: &Zone<'_>) -> Option<Placement> {
+        self.placements.remove(&zone_key(zone))
+    }
+
+    /// Returns the placement of `zone`, if it was placed.
+    pub fn placement(&self, zone: &Zone<'_>) -> Option<&Placement> {
+        self.placements.get(&zone_key(zone))
+    }
+
+    /// Returns the position of every LED in `zone`, ordered by LED index in the zone.
+    pub fn zone_points(&self, zone: &Zone<'_>) -> Vec<Point> {
+        match self.placement(zone) {
+            Some(placement) => placement.points(zone),
+            None => Placement::default().points(zone),
+        }
+    }
+
+    /// Returns the position of every LED of `controller`, ordered by LED index.
+    pub fn controller_points(&self, controller: &Controller) -> Vec<Point> {
+        controller
+            .get_all_zones()
+            .flat_map(|zone| self.zone_points(&zone))
+            .collect()
+    }
+
+    /// Returns the smallest and largest coordinates of all LEDs in `group`.
+    ///
+    /// Returns `None` if `group` has no LEDs.
+    pub fn bounds(&self, group: &ControllerGroup) -> Option<(Point, Point)> {
+        group
+            .iter()
+            .flat_map(|c| self.controller_points(c))
+            .fold(None, |acc, p| match acc {
+                None => Some((p, p)),
+                Some((min, max)) => Some((min.min(p), max.max(p))),
+            })
+    }
+
+    /// Returns the positions of all LEDs in `group`, one `Vec` per controller in group order.
+    ///
+    /// The coordinates are normalised to `0.0..=1.0` over the bounds of the group,
+    /// the index counts through all LEDs of the group.
+    pub fn led_positions(&self, group: &ControllerGroup) -> Vec<Vec<LedPosition>> {
+        let points = group
+            .iter()
+            .map(|c| self.controller_points(c))
+            .collect::<Vec<_>>();
+        let count = points.iter().map(Vec::len).sum();
+        let Some((min, max)) = self.bounds(group) else {
+            return points.iter().map(|_| Vec::new()).collect();
+        };
+        let size = max - min;
+        let norm = |v: f32, min: f32, size: f32| if size > 0.0 { (v - min) / size } else { 0.0 };
+
+        let mut index = 0;
+        points
+            .into_iter()
+            .map(|controller_points| {
+                controller_points
+                    .into_iter()
+                    .map(|p| {
+                        let pos = LedPosition {
+                            index,
+                            count,
+                            x: norm(p.x, min.x, size.x),
+                            y: norm(p.y, min.y, size.y),
+                            z: norm(p.z, min.z, size.z),
+                            is_matrix: size.y > 0.0,
+                        };
+                        index += 1;
+                        pos
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn zone_key(zone: &Zone<'_>) -> (DeviceId, String) {
+    (zone.controller().device_id(), zone.name().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_ops() {
+        let a = Point::new(1.0, 2.0, 2.0);
+        assert!((Point::ORIGIN.distance(a) - 3.0).abs() < f32::EPSILON);
+        assert_eq!(a * 2.0 - a, a);
+        assert_eq!(Point::ORIGIN.lerp(a, 0.5), Point::new(0.5, 1.0, 1.0));
+        assert_eq!(a.min(Point::ORIGIN), Point::ORIGIN);
+        assert_eq!(a.max(Point::ORIGIN), a);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{Zone, ZoneType, effects::normalise};
+
+use super::Point;
+
+/// Describes where the LEDs of a zone are located in the layout space.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Placement {
+    /// Places the zone on a (possibly rotated) rectangle.
+    ///
+    /// Matrix zones (e.g. keyboards) use their matrix map,
+    /// the top left LED is placed at `origin` and the bottom right LED at `origin + across + down`.
+    /// LEDs of other zones are spread evenly from `origin` to `origin + across`.
+    Grid {
+        /// Position of the first LED or top left key.
+        origin: Point,
+        /// Vector spanning the full width of the zone.
+        across: Point,
+        /// Vector spanning the full height of the zone.
+        down: Point,
+    },
+
+    /// Spreads the LEDs evenly along a path through the given points, in LED order.
+    ///
+    /// The first LED is placed on the first point, the last LED on the last point.
+    Path(Vec<Point>),
+
+    /// Places every LED of the zone on the same point.
+    At(Point),
+}
+
+impl Default for Placement {
+    /// A unit square at the origin, matching the positions used by [`crate::effects`].
+    fn default() -> Self {
+        Self::Grid {
+            origin: Point::ORIGIN,
+            across: Point::new(1.0, 0.0, 0.0),
+            down: Point::new(0.0, 1.0, 0.0),
+        }
+    }
+}
+
+impl Placement {
+    /// Creates a path placement along a straight line from `start` to `end`.
+    pub fn line(start: Point, end: Point) -> Self {
+        Self::Path(vec![start, end])
+    }
+
+    /// Returns the position of every LED in `zone`, ordered by LED index in the zone.
+    pub fn points(&self, zone: &Zone<'_>) -> Vec<Point> {
+        let count = zone.num_leds();
+        match self {
+            Self::Grid {
+                origin,
+                across,
+                down,
+            } => {
+                let mut points = (0..count)
+                    .map(|i| *origin + *across * normalise(i, count))
+                    .collect::<Vec<_>>();
+                if zone.zone_type() != ZoneType::Matrix {
+                    return points;
+                }
+                let Some(matrix) = zone.matrix_data() else {
+                    return points;
+                };
+                let (height, width) = (matrix.num_rows(), matrix.num_columns());
+                for (row_idx, row) in matrix.rows_iter().enumerate() {
+                    for (col_idx, &led) in row.enumerate() {
+                        if let Some(point) = points.get_mut(led as usize) {
+                            *point = *origin
+                                + *across * normalise(col_idx, width)
+                                + *down * normalise(row_idx, height);
+                        }
+                    }
+                }
+                points
+            }
+            Self::Path(path) => path_points(path, count),
+            Self::At(point) => vec![*point; count],
+        }
+    }
+}
+
+/// Spreads `count` points evenly over the length of `path`.
+pub(crate) fn path_points(path: &[Point], count: usize) -> Vec<Point> {
+    let (Some(&first), Some(&last)) = (path.first(), path.last()) else {
+        return vec![Point::ORIGIN; count];
+    };
+    let lengths = path
+        .windows(2)
+        .map(|w| w[0].distance(w[1]))
+        .collect::<Vec<_>>();
+    let total = lengths.iter().sum::<f32>();
+    if total <= 0.0 {
+        return vec![first; count];
+    }
+
+    (0..count)
+        .map(|i| {
+            let mut remaining = normalise(i, count) * total;
+            for (w, len) in path.windows(2).zip(&lengths) {
+                if remaining <= *len {
+                    let t = if *len > 0.0 { remaining / len } else { 0.0 };
+                    return w[0].lerp(w[1], t);
+                }
+                remaining -= len;
+            }
+            last
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Point, b: Point) {
+        assert!(a.distance(b) < 1e-4, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_path_points() {
+        let path = [
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(2.0, 0.0, 0.0),
+            Point::new(2.0, 2.0, 0.0),
+        ];
+        let points = path_points(&path, 5);
+        assert_eq!(points.len(), 5);
+        assert_close(points[0], Point::new(0.0, 0.0, 0.0));
+        assert_close(points[1], Point::new(1.0, 0.0, 0.0));
+        assert_close(points[2], Point::new(2.0, 0.0, 0.0));
+        assert_close(points[3], Point::new(2.0, 1.0, 0.0));
+        assert_close(points[4], Point::new(2.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn test_degenerate_paths() {
+        assert_eq!(path_points(&[], 2), vec![Point::ORIGIN; 2]);
+        let p = Point::new(1.0, 2.0, 3.0);
+        assert_eq!(path_points(&[p, p], 3), vec![p; 3]);
+        assert_eq!(path_points(&[p, Point::ORIGIN], 1), vec![p]);
+    }
+}
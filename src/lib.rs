@@ -36,5 +36,9 @@ pub use {
 pub(crate) use protocol::*;
 
 mod client;
+pub mod color;
+pub mod effects;
 mod error;
+pub mod layout;
+pub mod profile_file;
 pub(crate) mod protocol;
@@ -0,0 +1,395 @@
+//! Reading and writing `OpenRGB` profile (`.orp`) files.
+//!
+//! `OpenRGB` stores the profiles created with [`crate::OpenRgbClient::save_profile()`]
+//! as `.orp` files in its configuration directory. These files contain the controller data
+//! of every device, in the same format the SDK uses to send controller data.
+//!
+//! This module lets you inspect, edit, merge and generate these files offline.
+//! Once written to the `OpenRGB` configuration directory,
+//! the profile can be loaded with [`crate::OpenRgbClient::load_profile()`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use openrgb2::*;
+//! # fn example() -> OpenRgbResult<()> {
+//! let mut profile = profile_file::read("Default.orp")?;
+//! for controller in profile.controllers_mut() {
+//!     // set every device to its first mode and make the LEDs red
+//!     controller.set_active_mode(0)?;
+//!     controller.colors_mut().fill(Color::new(255, 0, 0));
+//! }
+//! profile_file::write("Red.orp", &profile)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::Path;
+
+use crate::{
+    Color, Controller, ControllerGroup, DeviceType, ModeData, OpenRgbError, OpenRgbResult,
+    ReceivedMessage, WriteMessage, ZoneType,
+    data::{ControllerData, ZoneData},
+};
+
+/// Header at the start of every profile file.
+pub const HEADER: &[u8; 16] = b"OPENRGB_PROFILE\0";
+
+/// Reads the profile file at `path`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not a valid profile file.
+pub fn read(path: impl AsRef<Path>) -> OpenRgbResult<ProfileFile> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).map_err(|source| OpenRgbError::FileError {
+        path: path.display().to_string(),
+        source,
+    })?;
+    ProfileFile::from_bytes(&bytes)
+}
+
+/// Writes `profile` to a file at `path`.
+///
+/// # Errors
+///
+/// Returns an error if the profile cannot be serialized or the file cannot be written.
+pub fn write(path: impl AsRef<Path>, profile: &ProfileFile) -> OpenRgbResult<()> {
+    let path = path.as_ref();
+    std::fs::write(path, profile.to_bytes()?).map_err(|source| OpenRgbError::FileError {
+        path: path.display().to_string(),
+        source,
+    })
+}
+
+/// Contents of an `OpenRGB` profile file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileFile {
+    version: u32,
+    controllers: Vec<ProfileController>,
+}
+
+impl ProfileFile {
+    /// Creates an empty profile using protocol `version` for the controller data.
+    pub fn new(version: u32) -> Self {
+        Self {
+            version,
+            controllers: Vec::new(),
+        }
+    }
+
+    /// Creates a profile of the current state of all controllers in `group`.
+    ///
+    /// This uses the controller data as last fetched from the server
+    /// and the protocol version of the connection.
+    pub fn from_group(group: &ControllerGroup) -> Self {
+        let version = group
+            .iter()
+            .map(|c| c.proto().get_protocol_version())
+            .min()
+            .unwrap_or(crate::DEFAULT_PROTOCOL);
+        Self {
+            version,
+            controllers: group.iter().map(ProfileController::from).collect(),
+        }
+    }
+
+    /// Parses the contents of a profile file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a valid profile file.
+    pub fn from_bytes(bytes: &[u8]) -> OpenRgbResult<Self> {
+        let Some(data) = bytes.strip_prefix(HEADER) else {
+            return Err(OpenRgbError::ParseError(
+                "Profile file does not start with OPENRGB_PROFILE header".to_owned(),
+            ));
+        };
+        let mut msg = ReceivedMessage::new(data, 0);
+        let version = msg.read_u32()?;
+        let mut idx = size_of::<u32>();
+        let mut controllers = Vec::new();
+        while idx < data.len() {
+            let block = &data[idx..];
+            let size = ReceivedMessage::new(block, version).read_u32()? as usize;
+            if size < size_of::<u32>() || size > block.len() {
+                return Err(OpenRgbError::ParseError(format!(
+                    "Invalid controller data size {size} at offset {}",
+                    HEADER.len() + idx
+                )));
+            }
+            let data = ReceivedMessage::new(&block[..size], version).read_value()?;
+            controllers.push(ProfileController { data, version });
+            idx += size;
+        }
+        Ok(Self {
+            version,
+            controllers,
+        })
+    }
+
+    /// Serializes this profile to the contents of a profile file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a controller was read with an older protocol version than [`Self::version()`].
+    pub fn to_bytes(&self) -> OpenRgbResult<Vec<u8>> {
+        let mut buf = WriteMessage::new(self.version);
+        buf.write_slice(HEADER);
+        buf.write_u32(self.version);
+        for controller in &self.controllers {
+            if controller.version < self.version {
+                return Err(OpenRgbError::CommandError(format!(
+                    "Controller {} has protocol version {} data, profile needs version {}",
+                    controller.name(),
+                    controller.version,
+                    self.version
+                )));
+            }
+            buf.write_value(&controller.data)?;
+        }
+        Ok(buf.bytes().to_vec())
+    }
+
+    /// Returns the protocol version used for the controller data in this profile.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the controllers in this profile.
+    pub fn controllers(&self) -> &[ProfileController] {
+        &self.controllers
+    }
+
+    /// Returns the controllers in this profile.
+    pub fn controllers_mut(&mut self) -> &mut [ProfileController] {
+        &mut self.controllers
+    }
+
+    /// Returns the controller with the given `name` and `location`.
+    pub fn find(&self, name: &str, location: &str) -> Option<&ProfileController> {
+        self.controllers
+            .iter()
+            .find(|c| c.name() == name && c.location() == location)
+    }
+
+    /// Adds `controller` to this profile, replacing a controller with the same name and location.
+    pub fn insert(&mut self, controller: ProfileController) {
+        match self
+            .controllers
+            .iter_mut()
+            .find(|c| c.name() == controller.name() && c.location() == controller.location())
+        {
+            Some(existing) => *existing = controller,
+            None => self.controllers.push(controller),
+        }
+    }
+
+    /// Removes all controllers for which `f` returns false.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&ProfileController) -> bool,
+    {
+        self.controllers.retain(f);
+    }
+
+    /// Adds all controllers of `other` to this profile, see [`Self::insert()`].
+    pub fn merge(&mut self, other: Self) {
+        for controller in other.controllers {
+            self.insert(controller);
+        }
+    }
+}
+
+/// Controller data stored in a [`ProfileFile`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileController {
+    data: ControllerData,
+    /// Protocol version the data was read with.
+    version: u32,
+}
+
+impl From<&Controller> for ProfileController {
+    fn from(controller: &Controller) -> Self {
+        Self {
+            data: controller.data().clone(),
+            version: controller.proto().get_protocol_version(),
+        }
+    }
+}
+
+impl ProfileController {
+    delegate::delegate! {
+        to self.data {
+            /// Returns the name of this controller.
+            pub fn name(&self) -> &str;
+            /// Returns the type of this controller.
+            pub fn device_type(&self) -> DeviceType;
+            /// Returns the vendor of this controller.
+            pub fn vendor(&self) -> &str;
+            /// Returns the description of this controller.
+            pub fn description(&self) -> &str;
+            /// Returns the version of this controller.
+            pub fn version(&self) -> &str;
+            /// Returns the serial of this controller.
+            pub fn serial(&self) -> &str;
+            /// Returns the location of this controller.
+            pub fn location(&self) -> &str;
+            /// Returns the modes of this controller.
+            pub fn modes(&self) -> &[ModeData];
+            /// Returns the mode that is active in this profile.
+            pub fn active_mode(&self) -> Option<&ModeData>;
+            /// Returns the colors of all LEDs in this profile.
+            pub fn colors(&self) -> &[Color];
+            /// Returns the modes of this controller, to change their settings.
+            pub fn modes_mut(&mut self) -> &mut [ModeData];
+            /// Returns the colors of all LEDs, to change them.
+            pub fn colors_mut(&mut self) -> &mut [Color];
+        }
+    }
+
+    /// Returns the zones of this controller.
+    pub fn zones(&self) -> impl Iterator<Item = ProfileZone<'_>> {
+        self.data.zones().iter().map(|zone| ProfileZone { zone })
+    }
+
+    /// Sets the mode with index `mode_id` as the active mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `mode_id` is out of bounds.
+    pub fn set_active_mode(&mut self, mode_id: usize) -> OpenRgbResult<()> {
+        if !self.data.set_active_mode(mode_id) {
+            return Err(OpenRgbError::CommandError(format!(
+                "Mode {mode_id} not found in controller {}",
+                self.name()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Zone of a [`ProfileController`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProfileZone<'a> {
+    zone: &'a ZoneData,
+}
+
+impl ProfileZone<'_> {
+    delegate::delegate! {
+        to self.zone {
+            /// Returns the name of this zone.
+            pub fn name(&self) -> &str;
+            /// Returns the type of this zone.
+            pub fn zone_type(&self) -> ZoneType;
+            /// Returns the minimum number of LEDs if this zone is resizable.
+            pub fn leds_min(&self) -> usize;
+            /// Returns the maximum number of LEDs if this zone is resizable.
+            pub fn leds_max(&self) -> usize;
+            /// Returns the number of LEDs in this zone.
+            #[call(leds_count)]
+            pub fn num_leds(&self) -> usize;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A profile with a single Corsair Vengeance Pro RGB module, protocol version 3.
+    fn profile_bytes() -> Vec<u8> {
+        let mut controller = WriteMessage::new(3);
+        controller
+            .push_value(&DeviceType::DRam)
+            .unwrap()
+            .push_value(&"Corsair Vengeance Pro RGB")
+            .unwrap()
+            .push_value(&"Corsair")
+            .unwrap()
+            .push_value(&"Corsair Vengeance Pro RGB Device")
+            .unwrap()
+            .push_value(&"")
+            .unwrap()
+            .push_value(&"")
+            .unwrap()
+            .push_value(&"I2C: /dev/i2c-1, address 0x58")
+            .unwrap()
+            .push_value(&0_u16) // no modes
+            .unwrap()
+            .push_value(&0_i32) // active mode
+            .unwrap()
+            .push_value(&1_u16) // zones
+            .unwrap()
+            .push_value(&"Corsair Pro Zone")
+            .unwrap()
+            .push_value(&ZoneType::Linear)
+            .unwrap()
+            .push_value(&(10_u32, 10_u32, 10_u32))
+            .unwrap()
+            .push_value(&0_u16) // no matrix
+            .unwrap()
+            .push_value(&0_u16) // no LEDs
+            .unwrap()
+            .push_value(&vec![Color::new(255, 0, 0); 10])
+            .unwrap();
+
+        let mut bytes = HEADER.to_vec();
+        bytes.extend_from_slice(&3_u32.to_le_bytes());
+        bytes.extend_from_slice(&(controller.len() as u32 + 4).to_le_bytes());
+        bytes.extend_from_slice(controller.bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_read_write() -> OpenRgbResult<()> {
+        let bytes = profile_bytes();
+        let mut profile = ProfileFile::from_bytes(&bytes)?;
+        assert_eq!(profile.version(), 3);
+        assert_eq!(profile.controllers().len(), 1);
+        let controller = &profile.controllers()[0];
+        assert_eq!(controller.name(), "Corsair Vengeance Pro RGB");
+        assert_eq!(controller.device_type(), DeviceType::DRam);
+        assert_eq!(controller.colors(), [Color::new(255, 0, 0); 10]);
+        let zone = controller.zones().next().expect("zone");
+        assert_eq!((zone.name(), zone.num_leds()), ("Corsair Pro Zone", 10));
+        assert!(profile.controllers_mut()[0].set_active_mode(0).is_err());
+
+        assert_eq!(profile.to_bytes()?, bytes);
+
+        profile.controllers_mut()[0].colors_mut()[0] = Color::new(0, 0, 255);
+        let edited = ProfileFile::from_bytes(&profile.to_bytes()?)?;
+        assert_eq!(edited.controllers()[0].colors()[0], Color::new(0, 0, 255));
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge() -> OpenRgbResult<()> {
+        let mut profile = ProfileFile::from_bytes(&profile_bytes())?;
+        let mut other = profile.clone();
+        other.controllers_mut()[0]
+            .colors_mut()
+            .fill(Color::new(0, 255, 0));
+        profile.merge(other.clone());
+        assert_eq!(profile, other);
+        assert!(
+            profile
+                .find("Corsair Vengeance Pro RGB", "I2C: /dev/i2c-1, address 0x58")
+                .is_some()
+        );
+
+        // newer data can not be read from older controller data
+        let mut newer = ProfileFile::new(5);
+        newer.merge(profile);
+        assert!(newer.to_bytes().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(ProfileFile::from_bytes(b"NOT_A_PROFILE").is_err());
+        let mut bytes = profile_bytes();
+        bytes.truncate(bytes.len() - 4);
+        assert!(ProfileFile::from_bytes(&bytes).is_err());
+    }
+}
@@ -3,7 +3,7 @@ use flagset::{FlagSet, flags};
 use crate::OpenRgbResult;
 use crate::data::ProtocolOption;
 use crate::protocol::data::{Color, DeviceType, LedData, ModeData, ZoneData};
-use crate::protocol::{DeserFromBuf, ReceivedMessage};
+use crate::protocol::{DeserFromBuf, ReceivedMessage, SerToBuf, WriteMessage};
 
 flags! {
     /// RGB Controller flags.
@@ -27,7 +27,7 @@ flags! {
 /// RGB controller.
 ///
 /// See [Open SDK documentation](https://gitlab.com/CalcProgrammer1/OpenRGB/-/wikis/OpenRGB-SDK-Documentation#net_packet_id_request_controller_data) for more information.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub(crate) struct ControllerData {
     /// Controller type.
     device_type: DeviceType,
@@ -157,16 +157,31 @@ impl ControllerData {
     }
 
     /// Returns the alternate names for LEDs, only supported in protocol version 5 and above.
-    #[allow(unused, reason = "api not finalised yet")]
     pub fn led_alt_names(&self) -> Option<&[String]> {
         self.led_alt_names.value().map(|v| v.as_slice())
     }
 
     /// Returns the flags for this controller. Only supported in protocol version 5 and above.
-    #[allow(unused, reason = "flags might be used later")]
     pub fn flags(&self) -> Option<FlagSet<ControllerFlags>> {
         self.flags.value().copied()
     }
+
+    /// Sets the active mode index, returns false if `mode_id` is out of bounds.
+    pub(crate) fn set_active_mode(&mut self, mode_id: usize) -> bool {
+        let valid = mode_id < self.modes.len();
+        if valid {
+            self.active_mode = mode_id as i32;
+        }
+        valid
+    }
+
+    pub(crate) fn modes_mut(&mut self) -> &mut [ModeData] {
+        &mut self.modes
+    }
+
+    pub(crate) fn colors_mut(&mut self) -> &mut [Color] {
+        &mut self.colors
+    }
 }
 
 impl DeserFromBuf for ControllerData {
@@ -220,6 +235,34 @@ impl DeserFromBuf for ControllerData {
     }
 }
 
+impl SerToBuf for ControllerData {
+    fn serialize(&self, buf: &mut WriteMessage) -> OpenRgbResult<()> {
+        let mut data = WriteMessage::new(buf.protocol_version());
+        data.push_value(&self.device_type)?
+            .push_value(&self.name)?
+            .push_value(&self.vendor)?
+            .push_value(&self.description)?
+            .push_value(&self.version)?
+            .push_value(&self.serial)?
+            .push_value(&self.location)?
+            .push_value(&(self.modes.len() as u16))?
+            .push_value(&self.active_mode)?;
+        for mode in &self.modes {
+            data.write_value(mode)?;
+        }
+        data.push_value(&self.zones)?
+            .push_value(&self.leds)?
+            .push_value(&self.colors)?
+            .push_value(&self.led_alt_names)?
+            .push_value(&self.flags)?;
+
+        // data size includes the size field itself
+        buf.write_u32((data.len() + size_of::<u32>()) as u32);
+        buf.write_slice(data.bytes());
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::error::Error;
@@ -228,8 +271,8 @@ mod tests {
     use crate::data::ProtocolOption;
     use crate::protocol::data::ControllerData;
 
-    #[test]
-    fn test_read_001() -> Result<(), Box<dyn Error>> {
+    /// Controller data of a Thermaltake Riing, captured with protocol version 3.
+    fn thermaltake_riing() -> WriteMessage {
         // this message is protocol version 3
         let mut buf = WriteMessage::new(3);
         buf.write_u32(760);
@@ -265,6 +308,12 @@ mod tests {
             0, 0, 0, 0, 0, 0, 0, 16, 0, 82, 105, 105, 110, 103, 32, 67, 104, 97, 110, 110, 101,
             108, 32, 53, 0, 1, 0, 0, 0, 0, 0, 0, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         ]);
+        buf
+    }
+
+    #[test]
+    fn test_read_001() -> Result<(), Box<dyn Error>> {
+        let buf = thermaltake_riing();
         let mut msg = buf.to_received_msg();
         let c_data = msg.read_value::<ControllerData>()?;
 
@@ -276,6 +325,11 @@ mod tests {
         assert_eq!(c_data.location, "HID: /dev/hidraw10".to_owned());
         assert_eq!(c_data.active_mode, 0);
         assert_eq!(c_data.modes.len(), 8);
+        let static_mode = &c_data.modes[1];
+        assert_eq!(static_mode.name(), "Static");
+        assert_eq!(static_mode.colors_min(), Some(1));
+        assert_eq!(static_mode.colors_max(), Some(1));
+        assert_eq!(static_mode.colors().len(), 1);
         assert_eq!(c_data.zones.len(), 5);
         assert_eq!(c_data.leds.len(), 0);
         assert_eq!(c_data.colors.len(), 0);
@@ -284,4 +338,15 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_write_001() -> Result<(), Box<dyn Error>> {
+        let buf = thermaltake_riing();
+        let c_data = buf.to_received_msg().read_value::<ControllerData>()?;
+
+        let mut written = WriteMessage::new(3);
+        written.write_value(&c_data)?;
+        assert_eq!(written.bytes(), buf.bytes());
+        Ok(())
+    }
 }
@@ -1,5 +1,5 @@
 use crate::OpenRgbResult;
-use crate::protocol::{DeserFromBuf, ReceivedMessage};
+use crate::protocol::{DeserFromBuf, ReceivedMessage, SerToBuf, WriteMessage};
 
 /// A single LED.
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -25,6 +25,13 @@ impl DeserFromBuf for LedData {
     }
 }
 
+impl SerToBuf for LedData {
+    fn serialize(&self, buf: &mut WriteMessage) -> OpenRgbResult<()> {
+        buf.push_value(&self.name)?.push_value(&self.value)?;
+        Ok(())
+    }
+}
+
 impl LedData {
     /// Returns the name of the LED.
     pub fn name(&self) -> &str {
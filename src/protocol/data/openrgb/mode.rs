@@ -47,6 +47,7 @@ flags! {
 
 /// Direction for [`ModeData`].
 #[derive(Eq, PartialEq, Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     /// Left direction.
     #[default]
@@ -82,6 +83,7 @@ impl_enum_discriminant!(
 ///
 /// See [Open SDK documentation](https://gitlab.com/CalcProgrammer1/OpenRGB/-/wikis/OpenRGB-SDK-Documentation) for more information.
 #[derive(Eq, PartialEq, Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ColorMode {
     /// No color mode.
     #[default]
@@ -249,18 +251,45 @@ impl ModeData {
 
     /// Returns the direction of this mode.
     ///
-    /// If `ModeFlag::HasDirection` is not set, returns `None`.
+    /// If none of the `ModeFlag::HasDirection*` flags are set, returns `None`.
     pub fn direction(&self) -> Option<Direction> {
-        self.flags
-            .contains(ModeFlag::HasDirection)
-            .then_some(self.direction)
+        (!self.flags.is_disjoint(ModeFlag::HasDirection)).then_some(self.direction)
+    }
+
+    /// Returns the directions this mode supports, based on the `ModeFlag::HasDirection*` flags.
+    pub fn supported_directions(&self) -> Vec<Direction> {
+        [
+            (
+                ModeFlag::HasDirectionLR,
+                [Direction::Left, Direction::Right],
+            ),
+            (ModeFlag::HasDirectionUD, [Direction::Up, Direction::Down]),
+            (
+                ModeFlag::HasDirectionHV,
+                [Direction::Horizontal, Direction::Vertical],
+            ),
+        ]
+        .into_iter()
+        .filter(|(flag, _)| self.flags.contains(*flag))
+        .flat_map(|(_, dirs)| dirs)
+        .collect()
+    }
+
+    /// Returns true if this mode supports `dir`.
+    pub fn supports_direction(&self, dir: Direction) -> bool {
+        let flag = match dir {
+            Direction::Left | Direction::Right => ModeFlag::HasDirectionLR,
+            Direction::Up | Direction::Down => ModeFlag::HasDirectionUD,
+            Direction::Horizontal | Direction::Vertical => ModeFlag::HasDirectionHV,
+        };
+        self.flags.contains(flag)
     }
 
     /// Set the direction of this mode.
     ///
-    /// If `ModeFlag::HasDirection` is not set, this will not change the direction.
+    /// If the mode does not support `dir`, this will not change the direction.
     pub fn set_direction(&mut self, dir: Direction) {
-        if self.flags.contains(ModeFlag::HasDirection) {
+        if self.supports_direction(dir) {
             self.direction = dir;
         }
     }
@@ -277,16 +306,51 @@ impl ModeData {
 
     /// Returns the minimum number of colors for this mode.
     ///
-    /// Returns `None` if the mode does not have any colors.
+    /// Returns `None` if the mode does not have mode specific colors.
     pub fn colors_min(&self) -> Option<u32> {
-        (!self.colors.is_empty()).then_some(self.colors_min)
+        self.has_colors().then_some(self.colors_min)
     }
 
     /// Returns the maximum number of colors for this mode.
     ///
-    /// Returns `None` if the mode does not have any colors.
+    /// Returns `None` if the mode does not have mode specific colors.
     pub fn colors_max(&self) -> Option<u32> {
-        (!self.colors.is_empty()).then_some(self.colors_max)
+        self.has_colors().then_some(self.colors_max)
+    }
+
+    fn has_colors(&self) -> bool {
+        self.flags.contains(ModeFlag::HasModeSpecificColor) || !self.colors.is_empty()
+    }
+
+    /// Set the mode specific colors of this mode.
+    ///
+    /// If `ModeFlag::HasModeSpecificColor` is not set, this will not change the colors.
+    /// The number of colors is not validated, see [`crate::ControllerModeBuilder::set_colors()`].
+    pub fn set_colors(&mut self, colors: Vec<Color>) {
+        if self.flags.contains(ModeFlag::HasModeSpecificColor) {
+            self.colors = colors;
+        }
+    }
+
+    /// Returns true if this mode supports `color_mode`.
+    ///
+    /// [`ColorMode::None`] is always supported.
+    pub fn supports_color_mode(&self, color_mode: ColorMode) -> bool {
+        match color_mode {
+            ColorMode::None => true,
+            ColorMode::PerLED => self.flags.contains(ModeFlag::HasPerLEDColor),
+            ColorMode::ModeSpecific => self.flags.contains(ModeFlag::HasModeSpecificColor),
+            ColorMode::Random => self.flags.contains(ModeFlag::HasRandomColor),
+        }
+    }
+
+    /// Set the color mode of this mode.
+    ///
+    /// If the mode does not support `color_mode`, this will not change the color mode.
+    pub fn set_color_mode(&mut self, color_mode: ColorMode) {
+        if self.supports_color_mode(color_mode) {
+            self.color_mode = color_mode;
+        }
     }
 }
 
@@ -299,10 +363,10 @@ impl DeserFromBuf for ModeData {
         let speed_max = buf.read_value()?;
         let brightness_min = buf.read_value()?;
         let brightness_max = buf.read_value()?;
-        let brightness = buf.read_value()?;
         let colors_min = buf.read_value()?;
         let colors_max = buf.read_value()?;
         let speed = buf.read_value()?;
+        let brightness = buf.read_value()?;
         let direction = buf.read_value::<Direction>()?;
         let color_mode = buf.read_value()?;
         let colors = buf.read_value::<Vec<Color>>()?;
@@ -336,10 +400,10 @@ impl SerToBuf for ModeData {
             .push_value(&self.speed_max)?
             .push_value(&self.brightness_min)?
             .push_value(&self.brightness_max)?
-            .push_value(&self.brightness)?
             .push_value(&self.colors_min)?
             .push_value(&self.colors_max)?
             .push_value(&self.speed)?
+            .push_value(&self.brightness)?
             .push_value(&self.direction)?
             .push_value(&self.color_mode)?
             .push_value(&self.colors)?;
@@ -430,10 +494,10 @@ mod tests {
             .push_value(&1000_u32)? // speed_max
             .push_value(&1_u32)? // brightness_min
             .push_value(&1024_u32)? // brightness_max
-            .push_value(&512_u32)? // brightness
             .push_value(&0_u32)? // colors_min
             .push_value(&256_u32)? // colors_max
             .push_value(&51_u32)? // speed
+            .push_value(&512_u32)? // brightness
             .push_value(&4_u32)? // direction
             .push_value(&1_u32)? // color_mode
             .push_value(&[
@@ -519,4 +583,78 @@ mod tests {
         assert_eq!(mode, msg.read_value::<ModeData>()?);
         Ok(())
     }
+
+    #[test]
+    fn test_write_field_order() -> Result<(), Box<dyn Error>> {
+        let mut mode = ModeData {
+            id: 0,
+            name: "Breathing".to_owned(),
+            value: 1,
+            flags: HasSpeed | HasBrightness | HasModeSpecificColor | HasRandomColor,
+            speed_min: 1,
+            speed_max: 5,
+            brightness_min: ProtocolOption::Some(0),
+            brightness_max: ProtocolOption::Some(100),
+            colors_min: 1,
+            colors_max: 2,
+            speed: 3,
+            brightness: ProtocolOption::Some(50),
+            direction: Direction::Left,
+            color_mode: ColorMode::Random,
+            colors: vec![Color::new(255, 0, 0)],
+        };
+        mode.set_colors(vec![Color::new(128, 0, 128)]);
+        mode.set_color_mode(ColorMode::ModeSpecific);
+        mode.set_color_mode(ColorMode::PerLED); // not supported, ignored
+
+        let mut buf = WriteMessage::new(3);
+        buf.write_value(&mode)?;
+        let mut msg = buf.to_received_msg();
+        assert_eq!(msg.read_value::<String>()?, "Breathing");
+        let values = (0..12)
+            .map(|_| msg.read_value::<u32>())
+            .collect::<Result<Vec<_>, _>>()?;
+        // value, flags, speed_min, speed_max, brightness_min, brightness_max,
+        // colors_min, colors_max, speed, brightness, direction, color_mode
+        assert_eq!(values, [1, 0b1101_0001, 1, 5, 0, 100, 1, 2, 3, 50, 0, 2]);
+        assert_eq!(msg.read_value::<Vec<Color>>()?, [Color::new(128, 0, 128)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_directions() {
+        let mut mode = ModeData {
+            id: 0,
+            name: "Wave".to_owned(),
+            value: 0,
+            flags: HasDirectionLR | HasSpeed,
+            speed_min: 0,
+            speed_max: 0,
+            brightness_min: ProtocolOption::UnsupportedVersion,
+            brightness_max: ProtocolOption::UnsupportedVersion,
+            colors_min: 0,
+            colors_max: 0,
+            speed: 0,
+            brightness: ProtocolOption::UnsupportedVersion,
+            direction: Direction::Left,
+            color_mode: ColorMode::None,
+            colors: Vec::new(),
+        };
+        assert_eq!(mode.direction(), Some(Direction::Left));
+        assert_eq!(
+            mode.supported_directions(),
+            [Direction::Left, Direction::Right]
+        );
+        assert!(!mode.supports_direction(Direction::Up));
+        mode.set_direction(Direction::Up);
+        assert_eq!(mode.direction(), Some(Direction::Left));
+        mode.set_direction(Direction::Right);
+        assert_eq!(mode.direction(), Some(Direction::Right));
+
+        mode.flags = HasDirection.into();
+        assert_eq!(mode.supported_directions().len(), 6);
+        mode.flags = HasSpeed.into();
+        assert_eq!(mode.direction(), None);
+        assert!(mode.supported_directions().is_empty());
+    }
 }
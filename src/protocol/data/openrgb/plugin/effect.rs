@@ -15,25 +15,26 @@ impl_enum_discriminant!(EffectsPluginPacket,
     StopEffect: 21
 );
 
-#[derive(Debug)]
-pub(crate) struct PluginEffect {
+/// An effect of the `OpenRGB` Effects plugin, see [`crate::EffectsPlugin`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginEffect {
     name: String,
     description: String,
     enabled: bool,
 }
 
 impl PluginEffect {
-    #[expect(unused, reason = "Plugin effect api todo")]
+    /// Returns the name of this effect, which is used to start and stop it.
     pub fn name(&self) -> &str {
         &self.name
     }
 
-    #[expect(unused, reason = "Plugin effect api todo")]
+    /// Returns the description of this effect.
     pub fn description(&self) -> &str {
         &self.description
     }
 
-    #[expect(unused, reason = "Plugin effect api todo")]
+    /// Returns true if this effect is enabled, i.e. running.
     pub fn enabled(&self) -> bool {
         self.enabled
     }
@@ -43,7 +44,6 @@ impl DeserFromBuf for PluginEffect {
     fn deserialize(buf: &mut ReceivedMessage<'_>) -> crate::OpenRgbResult<Self> {
         let name = buf.read_value()?;
         let description = buf.read_value()?;
-        tracing::trace!("buf after desc: {}", buf);
         let enabled = buf.read_u8()? != 0;
         Ok(PluginEffect {
             name,
@@ -52,3 +52,37 @@ impl DeserFromBuf for PluginEffect {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+    use crate::WriteMessage;
+
+    #[test]
+    fn test_read_effect_list() -> Result<(), Box<dyn Error>> {
+        let mut buf = WriteMessage::new(crate::DEFAULT_PROTOCOL);
+        let mut msg = buf
+            .push_value(&2_u16)?
+            .push_value(&"Rainbow Wave")?
+            .push_value(&"Rainbow moving across the devices")?
+            .push_value(&1_u8)?
+            .push_value(&"Breathing")?
+            .push_value(&"")?
+            .push_value(&0_u8)?
+            .to_received_msg();
+
+        let effects = msg.read_value::<Vec<PluginEffect>>()?;
+        assert_eq!(effects.len(), 2);
+        assert_eq!(effects[0].name(), "Rainbow Wave");
+        assert_eq!(
+            effects[0].description(),
+            "Rainbow moving across the devices"
+        );
+        assert!(effects[0].enabled());
+        assert_eq!(effects[1].name(), "Breathing");
+        assert!(!effects[1].enabled());
+        Ok(())
+    }
+}
@@ -1,12 +1,11 @@
 mod effect;
 
-#[expect(unused_imports, reason = "Plugin effect api todo")]
 pub use effect::*;
 
 use crate::{DeserFromBuf, ReceivedMessage};
 
 /// Data for `OpenRGB` plugins.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PluginData {
     /// Plugin name
     name: String,
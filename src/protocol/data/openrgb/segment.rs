@@ -9,7 +9,7 @@ pub struct SegmentData {
     name: String,
     /// Segment type should be the same as its parent's zone type.
     ///
-    /// For now it is always `ZoneType::Linear`.
+    /// [`Self::new()`] uses `ZoneType::Linear`.
     seg_type: ZoneType,
     start_idx: u32,
     led_count: u32,
@@ -34,6 +34,11 @@ impl SegmentData {
         &self.name
     }
 
+    /// Returns the type of this segment.
+    pub fn seg_type(&self) -> ZoneType {
+        self.seg_type
+    }
+
     /// Returns the number of LEDs in this segment.
     pub fn led_count(&self) -> u32 {
         self.led_count
@@ -52,6 +57,10 @@ impl SegmentData {
     pub(crate) fn set_id(&mut self, id: usize) {
         self.id = id;
     }
+
+    pub(crate) fn set_seg_type(&mut self, seg_type: ZoneType) {
+        self.seg_type = seg_type;
+    }
 }
 
 impl DeserFromBuf for SegmentData {
@@ -2,7 +2,7 @@ use array2d::Array2D;
 use flagset::{FlagSet, flags};
 
 use crate::protocol::data::ProtocolOption;
-use crate::protocol::{DeserFromBuf, ReceivedMessage};
+use crate::protocol::{DeserFromBuf, ReceivedMessage, SerToBuf, WriteMessage};
 use crate::{OpenRgbResult, impl_enum_discriminant};
 
 use super::SegmentData;
@@ -10,12 +10,14 @@ use super::SegmentData;
 /// Type of zones available.
 ///
 /// See [Open SDK documentation](https://gitlab.com/CalcProgrammer1/OpenRGB/-/wikis/OpenRGB-SDK-Documentation#zone-data) for more information.
-#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ZoneType {
     /// Single zone.
     Single = 0,
 
     /// Linear zone.
+    #[default]
     Linear = 1,
 
     /// Matrix zone.
@@ -126,6 +128,11 @@ impl ZoneData {
     pub fn matrix(&self) -> Option<&Array2D<u32>> {
         self.matrix.as_ref()
     }
+
+    /// Returns the flags of this zone, only supported in protocol version 5 and above.
+    pub fn flags(&self) -> Option<FlagSet<ZoneFlags>> {
+        self.flags.value().copied()
+    }
 }
 
 impl DeserFromBuf for ZoneData {
@@ -169,6 +176,31 @@ impl DeserFromBuf for ZoneData {
     }
 }
 
+impl SerToBuf for ZoneData {
+    fn serialize(&self, buf: &mut WriteMessage) -> OpenRgbResult<()> {
+        buf.push_value(&self.name)?
+            .push_value(&self.zone_type)?
+            .push_value(&self.leds_min)?
+            .push_value(&self.leds_max)?
+            .push_value(&self.leds_count)?;
+        match &self.matrix {
+            None => buf.write_u16(0),
+            Some(matrix) => {
+                // height and width, followed by the map
+                let len = 2 + matrix.num_elements();
+                buf.write_u16((len * size_of::<u32>()) as u16);
+                buf.write_u32(matrix.num_rows() as u32);
+                buf.write_u32(matrix.num_columns() as u32);
+                for value in matrix.elements_row_major_iter() {
+                    buf.write_u32(*value);
+                }
+            }
+        }
+        buf.push_value(&self.segments)?.push_value(&self.flags)?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::error::Error;
@@ -361,7 +361,14 @@ impl OpenRgbProtocol {
             .await
     }
 
-    fn check_protocol_version(&self, min: u32, msg: &str) -> OpenRgbResult<()> {
+    /// Returns true if the server notified a device list update since the last call.
+    ///
+    /// Notifications are only seen while reading responses of other requests.
+    pub async fn take_device_list_updated(&self) -> bool {
+        self.stream.lock().await.take_device_list_updated()
+    }
+
+    pub(crate) fn check_protocol_version(&self, min: u32, msg: &str) -> OpenRgbResult<()> {
         if self.protocol_id < min {
             return Err(OpenRgbError::UnsupportedOperation {
                 operation: msg.to_owned(),
@@ -374,7 +381,6 @@ impl OpenRgbProtocol {
 
     /* EFFECTS PLUGIN */
 
-    #[expect(unused, reason = "Plugin effect api todo")]
     pub async fn effect_plugin_get_effects(
         &self,
         effects_plugin_id: u32,
@@ -389,7 +395,6 @@ impl OpenRgbProtocol {
         Ok(list)
     }
 
-    #[expect(unused, reason = "Plugin effect api todo")]
     pub async fn effect_plugin_start_effect(
         &self,
         effect_plugin_id: u32,
@@ -403,7 +408,6 @@ impl OpenRgbProtocol {
         .await
     }
 
-    #[expect(unused, reason = "Plugin effect api todo")]
     pub async fn effect_plugin_stop_effect(
         &self,
         effect_plugin_id: u32,
@@ -618,13 +622,23 @@ mod tests {
         let client = OpenRgbProtocol::connect_to(DEFAULT_ADDR, DEFAULT_PROTOCOL).await?;
         let plugins = client.get_plugins().await?;
         println!("plugins: {0:?}", plugins);
-
-        // let effects = client.effect_plugin_get_effects(0).await?;
-        // println!("effects: {0:?}", effects);
-
-        // client.effect_plugin_stop_effect(0, effects[0].name()).await?;
-        // tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-        // client.effect_plugin_start_effect(0, effects[0].name()).await?;
+        let Some(plugin) = plugins
+            .iter()
+            .find(|p| p.name() == "OpenRGB Effects Plugin")
+        else {
+            return Ok(());
+        };
+
+        let effects = client.effect_plugin_get_effects(plugin.index()).await?;
+        println!("effects: {0:?}", effects);
+
+        client
+            .effect_plugin_stop_effect(plugin.index(), effects[0].name())
+            .await?;
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        client
+            .effect_plugin_start_effect(plugin.index(), effects[0].name())
+            .await?;
 
         Ok(())
     }
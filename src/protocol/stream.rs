@@ -76,9 +76,14 @@ impl OpenRgbMessageHeader {
 
 /// `tokio TcpStream` with an `OpenRGB` protocol version.
 /// The version is tagged to all received and written packets, since packet format depends on protocol version.
+///
+/// The server sends [`PacketId::DeviceListUpdated`] whenever its device list changes,
+/// without it being requested. These notifications are skipped while reading and remembered,
+/// see [`Self::take_device_list_updated()`].
 pub(crate) struct ProtocolStream {
     stream: TcpStream,
     protocol_version: u32,
+    device_list_updated: bool,
 }
 
 impl ProtocolStream {
@@ -90,6 +95,7 @@ impl ProtocolStream {
         Ok(Self {
             stream,
             protocol_version,
+            device_list_updated: false,
         })
     }
 
@@ -101,6 +107,11 @@ impl ProtocolStream {
         self.protocol_version = version;
     }
 
+    /// Returns true if a device list update was received since the last call.
+    pub fn take_device_list_updated(&mut self) -> bool {
+        std::mem::take(&mut self.device_list_updated)
+    }
+
     pub async fn request<I: SerToBuf, O: DeserFromBuf>(
         &mut self,
         device_id: u32,
@@ -152,7 +163,14 @@ impl ProtocolStream {
         device_id: u32,
         packet_id: PacketId,
     ) -> OpenRgbResult<OpenRgbMessageHeader> {
-        let header = OpenRgbMessageHeader::read(&mut self.stream).await?;
+        let mut header = OpenRgbMessageHeader::read(&mut self.stream).await?;
+        while header.packet_id == PacketId::DeviceListUpdated && packet_id != header.packet_id {
+            tracing::debug!("Received device list update");
+            self.device_list_updated = true;
+            let mut buf = vec![0u8; header.packet_size as usize];
+            self.stream.read_exact(&mut buf).await?;
+            header = OpenRgbMessageHeader::read(&mut self.stream).await?;
+        }
         if header.packet_id != packet_id {
             return Err(OpenRgbError::ProtocolError(format!(
                 "Unexpected packet ID: expected {:?}, got {:?}",